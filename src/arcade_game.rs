@@ -1,70 +1,252 @@
+use ai::AiPlugin;
+use animation::AnimationPlugin;
+use artillery::ArtilleryPlugin;
 use bevy::{app::AppExit, prelude::*};
-// use bevy_inspector_egui::quick::WorldInspectorPlugin;
-use combat::CombatPlugin;
+use bevy_ecs_ldtk::prelude::LevelSelection;
+use combat::{events::CharacterDiedEvent, CombatPlugin};
+#[cfg(feature = "dev")]
+use console::ConsolePlugin;
+#[cfg(feature = "dev")]
+use dev::DevPlugin;
 use iyes_loopless::prelude::*;
 use ldtk::LdtkMapBackendPlugin;
+use lighting::LightingPlugin;
+use loading::LoadingPlugin;
+use locale::LocalePlugin;
+use parallax::ParallaxPlugin;
+use particle::ParticlePlugin;
 use physics::{events::*, PhysicsPlugin};
+use pixel_perfect::PixelPerfectPlugin;
 use player::PlayerPlugin;
-use ui::{main_menu, pause_menu, UIPlugin};
+use replay::{components::ReplayPlayback, ReplayPlugin};
+use rng::{GameRng, ParticleRng};
+use save::SavePlugin;
+use settings::Settings;
+use sound::SoundPlugin;
+use std::{path::PathBuf, time::Duration};
+use tuning::TuningPlugin;
+use ui::{loading_screen, main_menu, menu, pause_menu, UIPlugin};
+use versus::VersusPlugin;
+use window_icon::WindowIconPlugin;
 
+mod ai;
+mod animation;
+mod artillery;
 mod character;
 mod combat;
+#[cfg(feature = "dev")]
+mod console;
+#[cfg(feature = "dev")]
+pub mod dev;
 mod enemy;
+#[cfg(test)]
+mod harness;
 mod ldtk;
+mod lighting;
+mod loading;
+mod locale;
 mod map;
+mod parallax;
+mod particle;
 mod physics;
+mod pixel_perfect;
 mod player;
+mod replay;
+mod rng;
+mod save;
+pub mod settings;
+mod sound;
+mod tuning;
 mod ui;
+mod versus;
+mod window_icon;
 
-pub struct ArcadeGame;
+/// The whole game as a single plugin.
+///
+/// `headless` leaves out everything that needs a window, a GPU or an audio device
+/// (menus, sound, collider rendering) so the simulation can run under `MinimalPlugins`.
+/// `replay` skips the main menu and plays the given replay file back.
+/// `level`, `map` and `seed` replace the first level, the LDtk project and the gameplay
+/// seed; `skip_menu` starts playing right away.
+#[derive(Default)]
+pub struct ArcadeGame {
+    pub headless: bool,
+    pub replay: Option<PathBuf>,
+    pub settings: Settings,
+    pub level: Option<LevelSelection>,
+    pub map: Option<String>,
+    pub seed: Option<u64>,
+    pub skip_menu: bool,
+}
 
 impl Plugin for ArcadeGame {
     fn build(&self, app: &mut App) {
-        app.insert_resource(ClearColor(CLEAR_COLOR))
+        app.add_fixed_timestep(Duration::from_secs_f32(FIXED_TIMESTEP), FIXED_UPDATE)
+            // one child stage per `FixedStep` after `Gameplay`
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .add_fixed_timestep_child_stage(FIXED_UPDATE)
+            .insert_resource(ClearColor(CLEAR_COLOR))
             .insert_resource(MapLevel::default())
+            .insert_resource(self.settings.clone())
+            .init_resource::<CameraScale>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameRng>()
+            .init_resource::<ParticleRng>()
             .add_loopless_state(GameState::MainMenu)
-            .add_plugin(UIPlugin)
+            .add_loopless_state(SceneState::Empty)
             .add_plugin(LdtkMapBackendPlugin)
+            .add_plugin(LoadingPlugin)
             .add_plugin(PlayerPlugin)
             .add_plugin(CombatPlugin)
-            .add_plugin(PhysicsPlugin)
-            .add_event::<CleanupSceneEvent>()
-            .add_event::<SetupSceneEvent>()
+            .add_plugin(AiPlugin)
+            .add_plugin(ArtilleryPlugin)
+            .add_plugin(VersusPlugin)
+            .add_plugin(ParticlePlugin)
+            .add_plugin(ParallaxPlugin)
+            .add_plugin(AnimationPlugin)
+            .add_plugin(PhysicsPlugin {
+                debug_render: !self.headless && cfg!(feature = "dev"),
+            })
+            .add_plugin(SavePlugin)
+            .add_plugin(TuningPlugin)
+            .add_plugin(ReplayPlugin {
+                file: self.replay.clone(),
+            })
             .add_startup_system(spawn_camera)
+            .add_system(fit_camera_scale)
             //
-            // map -> main menu transition
+            // scene lifecycle: spawn everything on loading, start simulating once it has all
+            // loaded, tear it all down on unloading
             .add_enter_system_set(
-                GameState::InGame,
-                ConditionSet::new()
-                    .run_if(setup_requested)
+                SceneState::Loading,
+                SystemSet::new()
                     .with_system(ldtk::setup)
-                    .with_system(physics::unpause_physics)
-                    .into(),
+                    .with_system(ldtk::reset_destroyed_terrain.run_if_not(arcade_mode))
+                    .with_system(rng::reset_rng)
+                    .with_system(artillery::reset_turns)
+                    .with_system(reset_camera)
+                    .with_system(versus::setup_players)
+                    .with_system(replay::start_recording)
+                    .with_system(physics::pause_physics),
             )
-            //
-            // main menu -> map transition
-            .add_exit_system_set(
-                GameState::Pause,
-                ConditionSet::new()
-                    .run_if(cleanup_requested)
+            .add_enter_system(SceneState::Playing, physics::unpause_physics)
+            .add_enter_system_set(
+                SceneState::Unloading,
+                SystemSet::new()
+                    .with_system(keep_arcade_run.run_if(arcade_mode))
+                    .with_system(replay::stop_playback)
                     .with_system(ldtk::cleanup)
                     .with_system(combat::cleanup_projectiles)
-                    .into(),
+                    .with_system(particle::cleanup_particles)
+                    .with_system(versus::despawn_player_cameras),
             )
+            .add_system(finish_unloading.run_in_state(SceneState::Unloading))
+            .add_system(
+                restart_on_defeat
+                    .run_in_state(GameState::InGame)
+                    .run_in_state(SceneState::Playing)
+                    .run_if(arcade_mode),
+            );
+
+        // headless runs are tests and tools, they must not leave save or replay files behind
+        if !self.headless {
+            app.add_enter_system_set(
+                SceneState::Unloading,
+                SystemSet::new()
+                    .with_system(save::autosave.run_if(arcade_mode))
+                    .with_system(replay::save_recording),
+            );
+        }
+
+        // launch options override what the plugins above set up
+        if let Some(level) = &self.level {
+            app.insert_resource(level.clone())
+                .insert_resource(ldtk::StartLevel(level.clone()));
+        }
+        if let Some(map) = &self.map {
+            app.insert_resource(ldtk::MapPath(map.clone()));
+        }
+        if let Some(seed) = self.seed {
+            app.insert_resource(GameRng::from_seed(seed));
+        }
+        if self.skip_menu && self.replay.is_none() {
+            app.add_startup_system(start_game);
+        }
+
+        if self.headless {
+            return;
+        }
+        #[cfg(feature = "dev")]
+        app.add_plugin(DevPlugin).add_plugin(ConsolePlugin);
+        if self.settings.window.pixel_perfect {
+            app.add_plugin(PixelPerfectPlugin);
+        }
+        app.add_plugin(WindowIconPlugin)
+            .add_plugin(LocalePlugin)
+            .add_plugin(LightingPlugin)
+            .add_plugin(UIPlugin)
+            .add_plugin(SoundPlugin)
             //
             // menu transitions
             .add_enter_system(GameState::MainMenu, main_menu::spawn_menu)
-            .add_exit_system(GameState::MainMenu, main_menu::despawn_menu)
+            .add_exit_system(GameState::MainMenu, menu::despawn_menu)
             .add_enter_system(GameState::Pause, pause_menu::spawn_menu)
-            .add_exit_system(GameState::Pause, pause_menu::despawn_menu)
+            .add_exit_system(GameState::Pause, menu::despawn_menu)
             //
             // pause <-> ingame transition
-            .add_system(handle_pause_game_input.run_in_state(GameState::InGame))
-            .add_system(handle_resume_game_input.run_in_state(GameState::Pause));
+            .add_system(
+                handle_pause_game_input
+                    .run_in_state(GameState::InGame)
+                    .run_in_state(SceneState::Playing),
+            )
+            .add_system(
+                handle_resume_game_input
+                    .run_in_state(GameState::Pause)
+                    .before(menu::navigate_menu),
+            )
+            .add_enter_system(SceneState::Loading, loading_screen::spawn_screen)
+            .add_exit_system(SceneState::Loading, loading_screen::despawn_screen)
+            .add_system(loading_screen::update_progress_bar.run_in_state(SceneState::Loading))
+            .add_enter_system(SceneState::Failed, loading_screen::spawn_error_screen)
+            .add_exit_system(SceneState::Failed, loading_screen::despawn_screen)
+            //
+            // replay pause/step/speed keys
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                replay::control_playback
+                    .run_in_state(GameState::InGame)
+                    .run_if_resource_exists::<ReplayPlayback>(),
+            );
     }
 }
 
 const CLEAR_COLOR: Color = Color::BLACK;
+/// World pixels the camera shows from top to bottom, whatever the window size.
+const VIEW_HEIGHT: f32 = 180.0;
+
+/// Length of a gameplay tick in seconds, shared by gameplay systems and Rapier.
+pub const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
+const FIXED_UPDATE: &str = "fixed_update";
+
+/// Substages of the fixed timestep, run in this order on every tick.
+#[derive(Clone, Copy)]
+enum FixedStep {
+    Gameplay,
+    PhysicsSync,
+    PhysicsSyncFlush,
+    PhysicsStep,
+    PhysicsWriteback,
+    Resolve,
+}
+
+impl FixedStep {
+    const fn substage(self) -> usize {
+        self as usize
+    }
+}
 
 #[derive(SystemLabel)]
 #[allow(dead_code)]
@@ -74,6 +256,17 @@ enum GameSystem {
     Combat,
 }
 
+/// Rules the next scene is played with, picked from the main menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Arcade,
+    /// Teams take turns to move and fire a single shot.
+    Artillery,
+    /// Two players on one keyboard, fighting each other.
+    Versus,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum GameState {
     MainMenu,
@@ -81,25 +274,85 @@ enum GameState {
     Pause,
 }
 
-// events
-pub struct CleanupSceneEvent;
-pub struct SetupSceneEvent;
+/// Lifecycle of the gameplay scene, independent of the menus shown on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SceneState {
+    /// Nothing of the scene exists.
+    Empty,
+    /// Assets are loading and the map and its entities are spawning.
+    Loading,
+    /// Everything has loaded and the level has spawned.
+    Playing,
+    /// An asset the scene needs is missing; waits for the player to go back to the menu.
+    Failed,
+    /// Everything the scene spawned is being despawned.
+    Unloading,
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+/// Zoom that fits `VIEW_HEIGHT` into the camera's target; modes that zoom the camera start
+/// from it.
+#[derive(Resource, Clone, Copy)]
+pub struct CameraScale(pub f32);
+
+impl Default for CameraScale {
+    /// The scale a 720 pixel high window gets.
+    fn default() -> Self {
+        Self(0.25)
+    }
+}
 
 #[derive(Resource, Default)]
 pub struct MapLevel {
     entity: Option<Entity>,
 }
 
-fn setup_requested(ev_setup: EventReader<SetupSceneEvent>) -> bool {
-    let res = !ev_setup.is_empty();
-    ev_setup.clear();
-    res
+/// The arcade run played last, kept when the scene unloads so "Play" picks it up again
+/// after other modes have reset the map.
+#[derive(Resource)]
+struct ArcadeRun {
+    level: LevelSelection,
+    destroyed_terrain: map::DestroyedTerrain,
+}
+
+/// Asks for the scene to be loaded again once it has unloaded, see [`restart_scene`].
+#[derive(Resource)]
+struct RestartScene;
+
+fn finish_unloading(restart: Option<Res<RestartScene>>, mut commands: Commands) {
+    if restart.is_some() {
+        commands.remove_resource::<RestartScene>();
+        commands.insert_resource(NextState(SceneState::Loading));
+    } else {
+        commands.insert_resource(NextState(SceneState::Empty));
+    }
+}
+
+/// Tears the scene down and loads it again from the start, in the same mode.
+pub fn restart_scene(commands: &mut Commands) {
+    commands.insert_resource(RestartScene);
+    commands.insert_resource(NextState(SceneState::Unloading));
 }
 
-fn cleanup_requested(ev_cleanup: EventReader<CleanupSceneEvent>) -> bool {
-    let res = !ev_cleanup.is_empty();
-    ev_cleanup.clear();
-    res
+/// Starts the level over when the player dies.
+fn restart_on_defeat(mut ev_died: EventReader<CharacterDiedEvent>, mut commands: Commands) {
+    if ev_died.iter().any(|died| died.player.is_some()) {
+        restart_scene(&mut commands);
+    }
+}
+
+fn arcade_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Arcade
+}
+
+fn artillery_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Artillery
+}
+
+fn versus_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Versus
 }
 
 fn handle_pause_game_input(
@@ -112,19 +365,91 @@ fn handle_pause_game_input(
     }
 }
 
+/// Escape in a submenu of the pause menu only backs out of it, see `navigate_menu`.
 fn handle_resume_game_input(
     input: Res<Input<KeyCode>>,
+    menu_stack: Res<menu::MenuStack>,
     mut commands: Commands,
     ev_unfreeze: EventWriter<UnfreezePhysicsEvent>,
 ) {
-    if input.just_pressed(KeyCode::Escape) {
+    if input.just_pressed(KeyCode::Escape) && !menu_stack.in_submenu() {
         resume_game(&mut commands, ev_unfreeze);
     }
 }
 
-pub fn start_game(mut commands: Commands, mut ev_writer_setup: EventWriter<SetupSceneEvent>) {
+pub fn start_game(commands: Commands) {
+    new_game(commands);
+}
+
+/// Starts a new arcade run: the launch level with an intact map, saving into a free slot.
+pub fn new_game(mut commands: Commands) {
+    commands.add(|world: &mut World| {
+        world.remove_resource::<ArcadeRun>();
+        begin_new_run(world);
+    });
+    play_mode(GameMode::Arcade, commands);
+}
+
+/// Starts a mode from the main menu. Arcade picks the last run back up, with the terrain
+/// it destroyed, or begins a new one if there is none; the other modes start on the
+/// launch level.
+pub fn start_mode(mode: GameMode, mut commands: Commands) {
+    commands.add(move |world: &mut World| {
+        if mode != GameMode::Arcade {
+            let level = world.resource::<ldtk::StartLevel>().0.clone();
+            world.insert_resource(level);
+            return;
+        }
+        match world.remove_resource::<ArcadeRun>() {
+            Some(run) => {
+                world.insert_resource(run.level);
+                world.insert_resource(run.destroyed_terrain);
+                world.insert_resource(save::components::PendingLoad::default());
+            }
+            None => begin_new_run(world),
+        }
+    });
+    play_mode(mode, commands);
+}
+
+fn begin_new_run(world: &mut World) {
+    world.insert_resource(save::components::ActiveSaveSlot(save::free_slot()));
+    world.insert_resource(save::components::PendingLoad::default());
+    world.insert_resource(map::DestroyedTerrain::default());
+    let level = world.resource::<ldtk::StartLevel>().0.clone();
+    world.insert_resource(level);
+}
+
+fn keep_arcade_run(
+    level_selection: Res<LevelSelection>,
+    destroyed_terrain: Res<map::DestroyedTerrain>,
+    mut commands: Commands,
+) {
+    commands.insert_resource(ArcadeRun {
+        level: level_selection.clone(),
+        destroyed_terrain: destroyed_terrain.clone(),
+    });
+}
+
+/// Enters the scene with whatever level and progress are set up.
+pub fn play_mode(mode: GameMode, mut commands: Commands) {
+    commands.insert_resource(mode);
+    commands.insert_resource(versus::components::VersusScore::default());
     commands.insert_resource(NextState(GameState::InGame));
-    ev_writer_setup.send(SetupSceneEvent);
+    commands.insert_resource(NextState(SceneState::Loading));
+}
+
+pub fn continue_game(slot: usize, mut commands: Commands) {
+    match save::read_slot(slot) {
+        Ok(data) => {
+            commands.insert_resource(LevelSelection::Identifier(data.level.clone()));
+            commands.insert_resource(save::components::ActiveSaveSlot(slot));
+            commands.insert_resource(data.destroyed_terrain());
+            commands.insert_resource(save::components::PendingLoad(Some(data)));
+            play_mode(GameMode::Arcade, commands);
+        }
+        Err(err) => error!("failed to load slot {}: {}", slot + 1, err),
+    }
 }
 
 pub fn pause_game(commands: &mut Commands, ev_freeze: EventWriter<FreezePhysicsEvent>) {
@@ -137,27 +462,69 @@ pub fn resume_game(commands: &mut Commands, ev_unfreeze: EventWriter<UnfreezePhy
     physics::unpause_physics(ev_unfreeze);
 }
 
-pub fn quit_to_main_menu(
-    mut commands: Commands,
-    mut ev_writer_cleanup: EventWriter<CleanupSceneEvent>,
-) {
+pub fn quit_to_main_menu(mut commands: Commands) {
+    commands.remove_resource::<RestartScene>();
     commands.insert_resource(NextState(GameState::MainMenu));
-    ev_writer_cleanup.send(CleanupSceneEvent);
+    commands.insert_resource(NextState(SceneState::Unloading));
 }
 
 pub fn quit_game(mut exit: EventWriter<AppExit>) {
     exit.send(AppExit);
 }
 
-fn spawn_camera(mut commands: Commands) {
+fn spawn_camera(mut commands: Commands, camera_scale: Res<CameraScale>) {
     commands
         .spawn(Camera2dBundle {
             transform: Transform::from_xyz(0.0, 0.0, 999.0),
             projection: OrthographicProjection {
-                scale: 0.25,
+                scale: camera_scale.0,
                 ..default()
             },
             ..default()
         })
-        .insert(Name::from("Camera"));
+        .insert((Name::from("Camera"), MainCamera));
+}
+
+/// Modes that move, zoom or split the camera leave it that way; start every scene from
+/// the default view.
+fn reset_camera(
+    camera_scale: Res<CameraScale>,
+    mut camera: Query<(&mut Camera, &mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    for (mut camera, mut transform, mut projection) in &mut camera {
+        camera.viewport = None;
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        projection.scale = camera_scale.0;
+    }
+}
+
+/// Keeps the camera showing `VIEW_HEIGHT` world pixels as its target is resized, in whole
+/// zoom steps with integer scaling. Drawing into the pixel perfect image always gives 1.
+fn fit_camera_scale(
+    settings: Res<Settings>,
+    mut camera_scale: ResMut<CameraScale>,
+    mut camera: Query<(&Camera, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    for (camera, mut projection) in &mut camera {
+        let (logical, physical) =
+            match (camera.logical_target_size(), camera.physical_target_size()) {
+                (Some(logical), Some(physical)) => (logical, physical),
+                _ => continue,
+            };
+        // minimised
+        if physical.y == 0 {
+            continue;
+        }
+        let scale = if settings.window.integer_scaling {
+            let zoom = (physical.y as f32 / VIEW_HEIGHT).floor().max(1.0);
+            physical.y as f32 / logical.y / zoom
+        } else {
+            VIEW_HEIGHT / logical.y
+        };
+        if camera_scale.0 != scale {
+            camera_scale.0 = scale;
+            projection.scale = scale;
+        }
+    }
 }
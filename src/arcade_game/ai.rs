@@ -0,0 +1,59 @@
+use crate::arcade_game::combat::prelude::CombatSystem;
+use crate::arcade_game::map::TILE_SIZE;
+use crate::arcade_game::{arcade_mode, FixedStep, GameState, GameSystem, SceneState, FIXED_UPDATE};
+use bevy::prelude::*;
+use components::AiSystem;
+use iyes_loopless::prelude::*;
+
+pub mod components;
+pub mod prelude;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_fixed_timestep_system(
+            FIXED_UPDATE,
+            FixedStep::Gameplay.substage(),
+            systems::detect_player
+                .run_in_state(GameState::InGame)
+                .run_in_state(SceneState::Playing)
+                .run_if(arcade_mode)
+                .label(AiSystem::Sense)
+                .after(GameSystem::Movement),
+        )
+        .add_fixed_timestep_system(
+            FIXED_UPDATE,
+            FixedStep::Gameplay.substage(),
+            systems::steer
+                .run_in_state(GameState::InGame)
+                .run_in_state(SceneState::Playing)
+                .run_if(arcade_mode)
+                .label(AiSystem::Act)
+                .after(AiSystem::Sense),
+        )
+        .add_fixed_timestep_system(
+            FIXED_UPDATE,
+            FixedStep::Gameplay.substage(),
+            // after the player's shot so both draw from the game RNG in a fixed order
+            systems::lob_projectiles
+                .run_in_state(GameState::InGame)
+                .run_in_state(SceneState::Playing)
+                .run_if(arcade_mode)
+                .label(AiSystem::Act)
+                .after(AiSystem::Sense)
+                .after(CombatSystem::Engage),
+        );
+    }
+}
+
+/// How far ahead of its collider an enemy looks for walls, in pixels.
+const WALL_PROBE: f32 = 1.0;
+/// Drops deeper than this are ledges that patrolling enemies turn back from.
+const LEDGE_PROBE: f32 = TILE_SIZE.y;
+/// Chasers stop this close to their target, in pixels.
+const CHASE_STOP_DISTANCE: f32 = 8.0;
+const PROJECTILE_SPIN: f32 = 30.0;
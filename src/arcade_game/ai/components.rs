@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+#[derive(SystemLabel)]
+pub enum AiSystem {
+    Sense,
+    Act,
+}
+
+/// Per-enemy AI state. What it does with it depends on its `AiParams::behavior`.
+#[derive(Component)]
+pub struct AiBrain {
+    pub forward: Vec2,
+    /// Position of the closest player in line of sight, if any.
+    pub target: Option<Vec2>,
+    /// Seconds until the next shot.
+    pub cooldown: f32,
+}
+
+impl Default for AiBrain {
+    fn default() -> Self {
+        Self {
+            forward: Vec2::NEG_X,
+            target: None,
+            cooldown: 0.0,
+        }
+    }
+}
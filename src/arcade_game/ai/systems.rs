@@ -0,0 +1,198 @@
+use crate::arcade_game::combat::ballistics;
+use crate::arcade_game::combat::prelude::*;
+use crate::arcade_game::enemy::prelude::*;
+use crate::arcade_game::physics::prelude::*;
+use crate::arcade_game::player::prelude::Player;
+use crate::arcade_game::rng::GameRng;
+use crate::arcade_game::tuning::components::Tuning;
+use crate::arcade_game::FIXED_TIMESTEP;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use super::components::*;
+
+/// Ray casts only hit terrain, the same way enemy colliders do.
+fn terrain_filter() -> QueryFilter<'static> {
+    QueryFilter::new().groups(DynamicColliderBundle::enemy_collision_groups())
+}
+
+fn ground_below(rapier_context: &RapierContext, origin: Vec2, half_extents: Vec2) -> bool {
+    rapier_context
+        .cast_ray(
+            origin,
+            Vec2::NEG_Y,
+            half_extents.y + super::LEDGE_PROBE,
+            true,
+            terrain_filter(),
+        )
+        .is_some()
+}
+
+/// Whether walking along `forward` would run into a wall or off a ledge.
+fn path_blocked(
+    rapier_context: &RapierContext,
+    position: Vec2,
+    half_extents: Vec2,
+    forward: Vec2,
+) -> bool {
+    let reach = half_extents.x + super::WALL_PROBE;
+    let wall = rapier_context
+        .cast_ray(position, forward, reach, true, terrain_filter())
+        .is_some();
+    wall || !ground_below(rapier_context, position + reach * forward, half_extents)
+}
+
+fn line_of_sight(rapier_context: &RapierContext, from: Vec2, to: Vec2) -> bool {
+    let offset = to - from;
+    rapier_context
+        .cast_ray(from, offset, 1.0, true, terrain_filter())
+        .is_none()
+}
+
+pub fn detect_player(
+    rapier_context: Res<RapierContext>,
+    players: Query<&GlobalTransform, With<Player>>,
+    mut enemies: Query<(&mut AiBrain, &AiParams, &GlobalTransform)>,
+) {
+    for (mut brain, params, transform) in &mut enemies {
+        let position = transform.translation().truncate();
+        brain.target = players
+            .iter()
+            .map(|player_transform| player_transform.translation().truncate())
+            .filter(|target| position.distance(*target) <= params.sight_range)
+            .filter(|target| line_of_sight(&rapier_context, position, *target))
+            .min_by(|a, b| position.distance(*a).total_cmp(&position.distance(*b)));
+    }
+}
+
+pub fn steer(
+    rapier_context: Res<RapierContext>,
+    mut enemies: Query<(
+        &mut AiBrain,
+        &AiParams,
+        &Moveable,
+        &GlobalTransform,
+        &Collider,
+        &mut KinematicCharacterController,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    for (mut brain, params, moveable, transform, collider, mut controller, mut sprite) in
+        &mut enemies
+    {
+        let position = transform.translation().truncate();
+        let half_extents = match collider.as_cuboid() {
+            Some(cuboid) => cuboid.half_extents(),
+            None => continue,
+        };
+        let to_target = brain.target.map(|target| target - position);
+        let direction = match (params.behavior, to_target) {
+            (AiBehavior::Idle, _) => None,
+            (AiBehavior::Chase, Some(to_target)) => {
+                if to_target.x.abs() <= super::CHASE_STOP_DISTANCE {
+                    None
+                } else {
+                    Some(Vec2::new(to_target.x.signum(), 0.0))
+                }
+            }
+            (AiBehavior::Artillery, Some(to_target))
+                if to_target.length() <= params.attack_range =>
+            {
+                None
+            }
+            _ => Some(brain.forward),
+        };
+        match direction {
+            Some(direction) => {
+                if !ground_below(&rapier_context, position, half_extents) {
+                    continue;
+                }
+                if path_blocked(&rapier_context, position, half_extents, direction) {
+                    // patrols turn around, chasers wait at the edge
+                    if brain.target.is_none() || params.behavior == AiBehavior::Patrol {
+                        brain.forward = -direction;
+                    }
+                } else {
+                    brain.forward = direction;
+                    let translation = moveable.speed * FIXED_TIMESTEP * direction;
+                    controller.translation = match controller.translation {
+                        Some(current) => Some(current + translation),
+                        None => Some(translation),
+                    };
+                }
+            }
+            None => {
+                if let Some(to_target) = to_target {
+                    if to_target.x != 0.0 {
+                        brain.forward = Vec2::new(to_target.x.signum(), 0.0);
+                    }
+                }
+            }
+        }
+        sprite.flip_x = brain.forward.x > 0.0;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn lob_projectiles(
+    mut game_rng: ResMut<GameRng>,
+    rapier_config: Res<RapierConfiguration>,
+    tuning: Res<Tuning>,
+    proj_sprite_sheet: Res<SpriteSheetHandle>,
+    proj_sprite_index: Res<ProjectileSpriteIndex>,
+    mut enemies: Query<(Entity, &mut AiBrain, &AiParams, &GlobalTransform)>,
+    mut ev_fired: EventWriter<ProjectileFiredEvent>,
+    mut commands: Commands,
+) {
+    for (entity, mut brain, params, transform) in &mut enemies {
+        brain.cooldown = (brain.cooldown - FIXED_TIMESTEP).max(0.0);
+        if params.behavior != AiBehavior::Artillery || brain.cooldown > 0.0 {
+            continue;
+        }
+        let target = match brain.target {
+            Some(target) => target,
+            None => continue,
+        };
+        let position = transform.translation().truncate();
+        if position.distance(target) > params.attack_range {
+            continue;
+        }
+        let forward = if target.x < position.x {
+            Vec2::NEG_X
+        } else {
+            Vec2::X
+        };
+        let muzzle = position + ballistics::MUZZLE_OFFSET * forward;
+        let angle = match ballistics::solve_launch_angle(
+            target - muzzle,
+            tuning.projectile_speed,
+            -rapier_config.gravity.y,
+        ) {
+            Some(angle) => angle,
+            None => continue,
+        };
+        commands
+            .spawn(
+                ProjectileBundle::new(
+                    &proj_sprite_sheet,
+                    &proj_sprite_index,
+                    muzzle.extend(transform.translation().z),
+                    Velocity {
+                        linvel: ballistics::launch_velocity(
+                            forward,
+                            angle,
+                            tuning.projectile_speed,
+                        ),
+                        angvel: game_rng
+                            .rng()
+                            .gen_range(-super::PROJECTILE_SPIN..super::PROJECTILE_SPIN),
+                    },
+                )
+                .fired_by(entity, DynamicColliderBundle::all_player_groups()),
+            )
+            .insert(ActiveEvents::COLLISION_EVENTS);
+        ev_fired.send(ProjectileFiredEvent { shooter: entity });
+        brain.cooldown = params.attack_cooldown;
+    }
+}
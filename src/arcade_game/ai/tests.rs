@@ -0,0 +1,43 @@
+use crate::arcade_game::enemy::prelude::*;
+use crate::arcade_game::harness::TestGame;
+use crate::arcade_game::player::prelude::Player;
+use bevy::prelude::*;
+
+const CHASE_FRAMES: usize = 60;
+/// On the ground left of the first level's hill, within sight of each other.
+const ENEMY_START: Vec2 = Vec2::new(60.0, 100.0);
+const PLAYER_START: Vec2 = Vec2::new(100.0, 100.0);
+
+fn chaser(world: &mut World) -> Option<Entity> {
+    world
+        .query::<(Entity, &AiParams)>()
+        .iter(world)
+        .find(|(_, params)| params.behavior == AiBehavior::Chase)
+        .map(|(entity, _)| entity)
+}
+
+fn place(world: &mut World, entity: Entity, position: Vec2) {
+    let mut transform = world.get_mut::<Transform>(entity).unwrap();
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+#[test]
+fn chasing_enemy_closes_in_on_the_player() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    let world = game.world();
+    let enemy = chaser(world).expect("the first level has no chasing enemy");
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    place(world, enemy, ENEMY_START);
+    place(world, player, PLAYER_START);
+
+    game.run(CHASE_FRAMES);
+    let end = game.world().get::<Transform>(enemy).unwrap().translation;
+    assert!(
+        end.x > ENEMY_START.x,
+        "enemy stayed at {} instead of moving towards {}",
+        end,
+        PLAYER_START
+    );
+}
@@ -0,0 +1,36 @@
+use crate::arcade_game::GameState;
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+use systems::*;
+
+pub mod components;
+pub mod prelude;
+mod systems;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(init_animators)
+            .add_system(
+                update_animation_state
+                    .run_in_state(GameState::InGame)
+                    .label(AnimationSystem::State),
+            )
+            .add_system(
+                trigger_shoot_animation
+                    .run_in_state(GameState::InGame)
+                    .label(AnimationSystem::Trigger)
+                    .after(AnimationSystem::State),
+            )
+            .add_system(
+                animate_sprites
+                    .run_in_state(GameState::InGame)
+                    .after(AnimationSystem::Trigger),
+            );
+    }
+}
+
+const DEFAULT_FRAME_TIME: f32 = 0.12;
+const AIRBORNE_VELOCITY_THRESHOLD: f32 = 1.0;
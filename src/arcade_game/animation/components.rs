@@ -0,0 +1,149 @@
+use crate::arcade_game::ldtk;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ecs_ldtk::prelude::*;
+use std::time::Duration;
+
+#[derive(SystemLabel)]
+pub enum AnimationSystem {
+    State,
+    Trigger,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnimationState {
+    #[default]
+    Idle,
+    Run,
+    Jump,
+    Shoot,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+    pub frames: Vec<usize>,
+    pub looping: bool,
+}
+
+#[derive(Component)]
+pub struct SpriteAnimator {
+    clips: HashMap<AnimationState, AnimationClip>,
+    state: AnimationState,
+    frame: usize,
+    timer: Timer,
+    base_index: Option<usize>,
+}
+
+impl Default for SpriteAnimator {
+    fn default() -> Self {
+        Self::new(super::DEFAULT_FRAME_TIME)
+    }
+}
+
+impl AnimationState {
+    fn looping(&self) -> bool {
+        !matches!(self, AnimationState::Shoot)
+    }
+}
+
+impl SpriteAnimator {
+    pub fn new(frame_time: f32) -> Self {
+        Self {
+            clips: HashMap::default(),
+            state: AnimationState::default(),
+            frame: 0,
+            timer: Timer::from_seconds(frame_time, TimerMode::Repeating),
+            base_index: None,
+        }
+    }
+
+    pub fn with_clip(mut self, state: AnimationState, frames: Vec<usize>) -> Self {
+        if !frames.is_empty() {
+            let looping = state.looping();
+            self.clips.insert(state, AnimationClip { frames, looping });
+        }
+        self
+    }
+
+    /// Switches to `state` unless a one-shot clip is still playing.
+    pub fn transition(&mut self, state: AnimationState) {
+        if self.state != state && !self.is_locked() {
+            self.play(state);
+        }
+    }
+
+    /// Switches to `state` immediately, restarting it if already playing.
+    pub fn play(&mut self, state: AnimationState) {
+        self.state = state;
+        self.frame = 0;
+        self.timer.reset();
+    }
+
+    pub fn is_locked(&self) -> bool {
+        match self.clips.get(&self.state) {
+            Some(clip) => !clip.looping && self.frame + 1 < clip.frames.len(),
+            None => false,
+        }
+    }
+
+    pub(super) fn set_base_index(&mut self, index: usize) {
+        self.base_index.get_or_insert(index);
+    }
+
+    /// Advances the current clip and returns the atlas index to display.
+    pub(super) fn tick(&mut self, delta: Duration) -> Option<usize> {
+        self.timer.tick(delta);
+        let (len, looping) = match self.clip() {
+            Some(clip) => (clip.frames.len(), clip.looping),
+            None => return self.base_index,
+        };
+        self.frame += self.timer.times_finished_this_tick() as usize;
+        if self.frame >= len {
+            if looping {
+                self.frame %= len;
+            } else {
+                // one-shot clips hand control back to the idle clip once done
+                self.play(AnimationState::Idle);
+            }
+        }
+        self.current_index()
+    }
+
+    /// States without a clip of their own fall back to the idle clip.
+    fn clip(&self) -> Option<&AnimationClip> {
+        self.clips
+            .get(&self.state)
+            .or_else(|| self.clips.get(&AnimationState::Idle))
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        match self.clip() {
+            Some(clip) => clip.frames.get(self.frame).copied(),
+            None => self.base_index,
+        }
+    }
+}
+
+impl From<&EntityInstance> for SpriteAnimator {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        let frame_time = ldtk::number_field(entity_instance, ldtk::FRAME_TIME_FIELD)
+            .unwrap_or(super::DEFAULT_FRAME_TIME);
+        SpriteAnimator::new(frame_time)
+            .with_clip(
+                AnimationState::Idle,
+                ldtk::int_array_field(entity_instance, ldtk::IDLE_FRAMES_FIELD),
+            )
+            .with_clip(
+                AnimationState::Run,
+                ldtk::int_array_field(entity_instance, ldtk::RUN_FRAMES_FIELD),
+            )
+            .with_clip(
+                AnimationState::Jump,
+                ldtk::int_array_field(entity_instance, ldtk::JUMP_FRAMES_FIELD),
+            )
+            .with_clip(
+                AnimationState::Shoot,
+                ldtk::int_array_field(entity_instance, ldtk::SHOOT_FRAMES_FIELD),
+            )
+    }
+}
@@ -0,0 +1,66 @@
+use crate::arcade_game::combat::events::ProjectileFiredEvent;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+
+pub fn init_animators(
+    mut animators: Query<(&mut SpriteAnimator, &TextureAtlasSprite), Added<SpriteAnimator>>,
+) {
+    for (mut animator, sprite) in &mut animators {
+        animator.set_base_index(sprite.index);
+    }
+}
+
+pub fn update_animation_state(
+    mut animators: Query<
+        (
+            &mut SpriteAnimator,
+            Option<&KinematicCharacterControllerOutput>,
+            Option<&Velocity>,
+        ),
+        With<KinematicCharacterController>,
+    >,
+) {
+    for (mut animator, output, velocity) in &mut animators {
+        let airborne = velocity
+            .map(|velocity| velocity.linvel.y.abs() > super::AIRBORNE_VELOCITY_THRESHOLD)
+            .unwrap_or(false);
+        // the controller's translation is consumed inside the fixed tick, so read back what moved
+        let moving = output
+            .map(|output| output.effective_translation.x != 0.0)
+            .unwrap_or(false);
+        let state = if airborne {
+            AnimationState::Jump
+        } else if moving {
+            AnimationState::Run
+        } else {
+            AnimationState::Idle
+        };
+        animator.transition(state);
+    }
+}
+
+pub fn trigger_shoot_animation(
+    mut ev_fired: EventReader<ProjectileFiredEvent>,
+    mut animators: Query<&mut SpriteAnimator>,
+) {
+    for fired in ev_fired.iter() {
+        if let Ok(mut animator) = animators.get_mut(fired.shooter) {
+            animator.play(AnimationState::Shoot);
+        }
+    }
+}
+
+pub fn animate_sprites(
+    time: Res<Time>,
+    mut animators: Query<(&mut SpriteAnimator, &mut TextureAtlasSprite)>,
+) {
+    for (mut animator, mut sprite) in &mut animators {
+        if let Some(index) = animator.tick(time.delta()) {
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::arcade_game::combat::prelude::CombatSystem;
+use crate::arcade_game::{artillery_mode, FixedStep, GameState, GameSystem, FIXED_UPDATE};
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+pub use systems::reset_turns;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// Turn-based mode: teams take turns to move and fire one shot each, with wind, until only
+/// one team has characters left.
+pub struct ArtilleryPlugin;
+
+impl Plugin for ArtilleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Turn>()
+            .init_resource::<Wind>()
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
+                systems::blow_wind
+                    .run_in_state(GameState::InGame)
+                    .run_if(artillery_mode)
+                    .after(GameSystem::Combat),
+            )
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Resolve.substage(),
+                systems::track_shots
+                    .run_in_state(GameState::InGame)
+                    .run_if(artillery_mode)
+                    .label(ArtillerySystem::TrackShots)
+                    .after(CombatSystem::Result),
+            )
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Resolve.substage(),
+                systems::advance_turns
+                    .run_in_state(GameState::InGame)
+                    .run_if(artillery_mode)
+                    .after(CombatSystem::Result)
+                    .after(ArtillerySystem::TrackShots),
+            )
+            .add_system(
+                systems::follow_action
+                    .run_in_state(GameState::InGame)
+                    .run_if(artillery_mode),
+            );
+    }
+}
+
+/// Seconds a team has to move and fire.
+const TURN_TIME: f32 = 30.0;
+/// Seconds to wait for a shot to land before handing over anyway.
+const RESOLVE_TIME: f32 = 10.0;
+/// Seconds the result is shown before going back to the main menu.
+const MATCH_END_TIME: f32 = 3.0;
+/// Strongest wind, as horizontal acceleration in pixels per second squared.
+const MAX_WIND: f32 = 40.0;
+/// How quickly the camera catches up with the action, per second.
+const CAMERA_FOLLOW_RATE: f32 = 4.0;
@@ -0,0 +1,70 @@
+use crate::arcade_game::character::Team;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Unbounded};
+
+#[derive(SystemLabel)]
+pub enum ArtillerySystem {
+    TrackShots,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TurnPhase {
+    /// The active character may move, aim and fire.
+    #[default]
+    Aiming,
+    /// A shot is in the air; the turn ends once it has landed.
+    Resolving,
+    /// One team is left standing; the match ends when the timer runs out.
+    Over,
+}
+
+#[derive(Resource, Default)]
+pub struct Turn {
+    pub number: u32,
+    pub team: Option<Team>,
+    pub active: Option<Entity>,
+    pub phase: TurnPhase,
+    pub timer: Timer,
+    /// How many turns each team has had, so its characters take turns in order.
+    pub rotation: HashMap<Team, usize>,
+    /// The last team standing, once the match is over.
+    pub winner: Option<Team>,
+}
+
+impl Turn {
+    /// Picks the next character, moving on to the next team after `self.team` that still
+    /// has characters. Teams and their characters are expected in a stable order.
+    pub fn pick_next(&mut self, teams: &BTreeMap<Team, Vec<Entity>>) -> Option<Entity> {
+        let (team, members) = match self.team {
+            Some(current) => teams.range((Excluded(current), Unbounded)).next(),
+            None => None,
+        }
+        .or_else(|| teams.iter().next())?;
+        let rotation = self.rotation.entry(*team).or_default();
+        let next = members[*rotation % members.len()];
+        *rotation += 1;
+        self.number += 1;
+        self.team = Some(*team);
+        self.active = Some(next);
+        Some(next)
+    }
+
+    /// Ends the match once at most one team has characters left. A level that starts with
+    /// a single team is played without an end.
+    pub fn decide(&mut self, teams: &BTreeMap<Team, Vec<Entity>>) -> bool {
+        if self.number == 0 || teams.len() > 1 {
+            return false;
+        }
+        self.winner = teams.keys().next().copied();
+        self.phase = TurnPhase::Over;
+        self.active = None;
+        self.timer = Timer::from_seconds(super::MATCH_END_TIME, TimerMode::Once);
+        true
+    }
+}
+
+/// Horizontal acceleration applied to projectiles in flight, changed every turn.
+#[derive(Resource, Default)]
+pub struct Wind(pub f32);
@@ -0,0 +1,140 @@
+use crate::arcade_game::character::{Character, Team};
+use crate::arcade_game::combat::prelude::*;
+use crate::arcade_game::player::prelude::*;
+use crate::arcade_game::rng::GameRng;
+use crate::arcade_game::{self, MainCamera, FIXED_TIMESTEP};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::components::*;
+
+pub fn reset_turns(mut turn: ResMut<Turn>, mut wind: ResMut<Wind>) {
+    *turn = Turn::default();
+    *wind = Wind::default();
+}
+
+pub fn blow_wind(wind: Res<Wind>, mut projectiles: Query<&mut Velocity, With<Projectile>>) {
+    for mut velocity in &mut projectiles {
+        velocity.linvel.x += wind.0 * FIXED_TIMESTEP;
+    }
+}
+
+/// Ends the active character's say once it has fired.
+pub fn track_shots(
+    mut turn: ResMut<Turn>,
+    mut ev_fired: EventReader<ProjectileFiredEvent>,
+    mut commands: Commands,
+) {
+    for fired in ev_fired.iter() {
+        if turn.phase == TurnPhase::Aiming && turn.active == Some(fired.shooter) {
+            turn.phase = TurnPhase::Resolving;
+            turn.timer = Timer::from_seconds(super::RESOLVE_TIME, TimerMode::Once);
+            commands.entity(fired.shooter).insert(InputLocked);
+        }
+    }
+}
+
+/// Hands control to the next team once the turn timer runs out or the shot has resolved,
+/// and ends the match once a single team is left.
+pub fn advance_turns(
+    mut turn: ResMut<Turn>,
+    mut wind: ResMut<Wind>,
+    mut game_rng: ResMut<GameRng>,
+    characters: Query<(Entity, &Team, Option<&Player>), With<Character>>,
+    player_children: Query<(Entity, &Parent), With<PlayerChild>>,
+    projectiles: Query<Entity, With<Projectile>>,
+    mut commands: Commands,
+) {
+    turn.timer.tick(Duration::from_secs_f32(FIXED_TIMESTEP));
+    if turn.phase == TurnPhase::Over {
+        if turn.timer.finished() {
+            arcade_game::quit_to_main_menu(commands);
+        }
+        return;
+    }
+    let active_alive = turn
+        .active
+        .map(|active| characters.contains(active))
+        .unwrap_or(false);
+    let turn_over = match turn.phase {
+        _ if !active_alive => true,
+        TurnPhase::Aiming => turn.timer.finished(),
+        TurnPhase::Resolving => projectiles.is_empty() || turn.timer.finished(),
+        TurnPhase::Over => false,
+    };
+    if !turn_over {
+        return;
+    }
+
+    let mut teams: BTreeMap<Team, Vec<Entity>> = BTreeMap::new();
+    for (entity, team, _) in &characters {
+        teams.entry(*team).or_default().push(entity);
+    }
+    for members in teams.values_mut() {
+        members.sort();
+    }
+    if turn.decide(&teams) {
+        match turn.winner {
+            Some(winner) => info!("team {} wins after {} turns", winner.0, turn.number),
+            None => info!("no team is left standing"),
+        }
+        for (entity, _, _) in &characters {
+            commands.entity(entity).insert(InputLocked);
+        }
+        return;
+    }
+    let next = match turn.pick_next(&teams) {
+        Some(next) => next,
+        None => return,
+    };
+    turn.phase = TurnPhase::Aiming;
+    turn.timer = Timer::from_seconds(super::TURN_TIME, TimerMode::Once);
+    wind.0 = game_rng.rng().gen_range(-super::MAX_WIND..=super::MAX_WIND);
+    info!(
+        "turn {}: team {} moves, wind {:.0}",
+        turn.number,
+        turn.team.map(|team| team.0).unwrap_or_default(),
+        wind.0
+    );
+
+    // shots that never landed don't carry over into the next turn
+    for projectile in &projectiles {
+        commands.entity(projectile).despawn_recursive();
+    }
+    for (entity, _, player) in &characters {
+        if player.is_some() && entity != next {
+            commands.entity(entity).remove::<Player>();
+            for (child, parent) in &player_children {
+                if parent.get() == entity {
+                    commands.entity(child).despawn_recursive();
+                }
+            }
+        }
+    }
+    // teams share player one's keys, hot-seat style
+    commands
+        .entity(next)
+        .remove::<InputLocked>()
+        .insert((Player::default(), PlayerId::ONE));
+}
+
+/// Keeps the camera on the shot in flight, or on the character whose turn it is.
+pub fn follow_action(
+    time: Res<Time>,
+    projectiles: Query<&GlobalTransform, With<Projectile>>,
+    player: Query<&GlobalTransform, With<Player>>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    let focus = match projectiles.iter().chain(player.iter()).next() {
+        Some(focus) => focus.translation().truncate(),
+        None => return,
+    };
+    let blend = 1.0 - (-super::CAMERA_FOLLOW_RATE * time.delta_seconds()).exp();
+    for mut transform in &mut camera {
+        let position = transform.translation.truncate().lerp(focus, blend);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
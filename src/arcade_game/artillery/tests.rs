@@ -0,0 +1,49 @@
+use super::components::{Turn, TurnPhase};
+use crate::arcade_game::character::Team;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+fn teams() -> BTreeMap<Team, Vec<Entity>> {
+    BTreeMap::from([
+        (Team(0), vec![Entity::from_raw(1), Entity::from_raw(2)]),
+        (Team(3), vec![Entity::from_raw(7)]),
+    ])
+}
+
+#[test]
+fn teams_alternate_and_characters_rotate_within_a_team() {
+    let teams = teams();
+    let mut turn = Turn::default();
+    let order: Vec<Entity> = (0..5).filter_map(|_| turn.pick_next(&teams)).collect();
+    assert_eq!(
+        order,
+        [1, 7, 2, 7, 1].map(Entity::from_raw).to_vec(),
+        "expected teams to alternate"
+    );
+    assert_eq!(turn.number, 5);
+}
+
+#[test]
+fn no_turn_without_characters() {
+    let mut turn = Turn::default();
+    assert_eq!(turn.pick_next(&BTreeMap::new()), None);
+    assert_eq!(turn.active, None);
+}
+
+#[test]
+fn match_ends_with_one_team_left() {
+    let mut teams = teams();
+    let mut turn = Turn::default();
+    assert!(
+        !turn.decide(&teams),
+        "a match needs a turn before it can end"
+    );
+    turn.pick_next(&teams);
+    assert!(!turn.decide(&teams));
+
+    teams.remove(&Team(3));
+    assert!(turn.decide(&teams));
+    assert_eq!(turn.phase, TurnPhase::Over);
+    assert_eq!(turn.winner, Some(Team(0)));
+    assert_eq!(turn.active, None);
+}
@@ -1,3 +1,5 @@
+use crate::arcade_game::animation::prelude::*;
+use crate::arcade_game::combat::prelude::{Damage, HitPoint};
 use crate::arcade_game::ldtk;
 use crate::arcade_game::physics::prelude::*;
 use crate::arcade_game::player::prelude::PlayerBundle;
@@ -11,6 +13,16 @@ pub const CHARACTER_SIZE: Vec2 = Vec2 { x: 8., y: 8. };
 #[reflect(Component)]
 pub struct Character;
 
+/// Side a character fights on in turn-based modes.
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[reflect(Component)]
+pub struct Team(pub u8);
+
+impl Team {
+    pub const PLAYERS: Team = Team(0);
+    pub const ENEMIES: Team = Team(1);
+}
+
 #[derive(Bundle, Default, LdtkEntity)]
 pub struct CharacterBundle {
     pub moveable: Moveable,
@@ -20,17 +32,51 @@ pub struct CharacterBundle {
     pub collider_bundle: DynamicColliderBundle,
     pub controller: KinematicCharacterController,
     pub velocity: Velocity,
+    pub animator: SpriteAnimator,
+    pub hit_points: HitPoint,
+    pub damage: Damage,
+    pub team: Team,
+}
+
+impl CharacterBundle {
+    pub const DEFAULT_HIT_POINTS: u8 = 100;
+    pub const DEFAULT_DAMAGE: u8 = 10;
+    pub const DEFAULT_MOVE_SPEED: f32 = 20.0;
 }
 
 impl From<EntityInstance> for CharacterBundle {
     fn from(entity_instance: EntityInstance) -> Self {
-        match entity_instance.identifier.as_ref() {
-            ldtk::PLAYER_ID => Self {
-                moveable: Moveable::new(PlayerBundle::DEFAULT_MOVE_SPEED),
-                collider_bundle: entity_instance.into(),
-                ..default()
-            },
-            _ => Self::default(),
+        let (default_moveable, default_team) = match entity_instance.identifier.as_ref() {
+            ldtk::PLAYER_ID => (
+                Moveable::tuned(PlayerBundle::DEFAULT_MOVE_SPEED),
+                Team::PLAYERS,
+            ),
+            _ => (
+                Moveable::new(CharacterBundle::DEFAULT_MOVE_SPEED),
+                Team::ENEMIES,
+            ),
+        };
+        let byte_field = |identifier: &str, default: u8| {
+            ldtk::int_field(&entity_instance, identifier)
+                .map(|value| value.clamp(0, u8::MAX as i32) as u8)
+                .unwrap_or(default)
+        };
+        Self {
+            moveable: ldtk::number_field(&entity_instance, ldtk::MOVE_SPEED_FIELD)
+                .map(Moveable::new)
+                .unwrap_or(default_moveable),
+            hit_points: HitPoint(byte_field(
+                ldtk::HIT_POINTS_FIELD,
+                CharacterBundle::DEFAULT_HIT_POINTS,
+            )),
+            damage: Damage(byte_field(
+                ldtk::DAMAGE_FIELD,
+                CharacterBundle::DEFAULT_DAMAGE,
+            )),
+            team: Team(byte_field(ldtk::TEAM_FIELD, default_team.0)),
+            animator: SpriteAnimator::from(&entity_instance),
+            collider_bundle: entity_instance.into(),
+            ..default()
         }
     }
 }
@@ -1,13 +1,17 @@
-use crate::arcade_game::{GameState, GameSystem};
+use crate::arcade_game::{FixedStep, GameState, GameSystem, FIXED_UPDATE};
 use bevy::prelude::*;
 use components::*;
 use iyes_loopless::prelude::*;
 pub use systems::cleanup_projectiles;
 use systems::*;
 
+pub mod ballistics;
 pub mod components;
+pub mod events;
 pub mod prelude;
 mod systems;
+#[cfg(test)]
+mod tests;
 
 pub struct CombatPlugin;
 
@@ -15,8 +19,15 @@ impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ProjectileSpriteIndex(DEFAULT_PROJECTILE_INDEX))
             .insert_resource(SpriteSheetHandle::default())
+            .init_resource::<FriendlyFire>()
+            .add_event::<ProjectileFiredEvent>()
+            .add_event::<TileDestroyedEvent>()
+            .add_event::<ExplosionEvent>()
+            .add_event::<CharacterDiedEvent>()
             .add_startup_system(setup)
-            .add_system(
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
                 aim_weapon
                     .run_in_state(GameState::InGame)
                     .label(GameSystem::Input)
@@ -24,7 +35,9 @@ impl Plugin for CombatPlugin {
                     .label(CombatSystem::Aim)
                     .after(GameSystem::Movement),
             )
-            .add_system(
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
                 fire_projectile
                     .run_in_state(GameState::InGame)
                     .label(GameSystem::Input)
@@ -32,14 +45,25 @@ impl Plugin for CombatPlugin {
                     .label(CombatSystem::Engage)
                     .after(CombatSystem::Aim),
             )
-            .add_system_set(
+            .add_fixed_timestep_system_set(
+                FIXED_UPDATE,
+                FixedStep::Resolve.substage(),
                 ConditionSet::new()
                     .run_in_state(GameState::InGame)
                     .label(GameSystem::Combat)
                     .label(CombatSystem::Result)
-                    .after(CombatSystem::Engage)
                     .with_system(deal_projectile_effect)
+                    .with_system(hit_characters)
                     .into(),
+            )
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Resolve.substage(),
+                remove_dead_characters
+                    .run_in_state(GameState::InGame)
+                    .label(GameSystem::Combat)
+                    .label(CombatSystem::Death)
+                    .after(CombatSystem::Result),
             );
     }
 }
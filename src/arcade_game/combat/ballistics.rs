@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+/// How far in front of the shooter projectiles are spawned.
+pub const MUZZLE_OFFSET: f32 = 4.0;
+
+fn dir_to_sign(dir: &Vec2) -> f32 {
+    if dir.x > 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+/// Velocity of a projectile fired along `forward` at `angle`, where positive angles aim
+/// below the horizon, matching `Player::projectile_angle`.
+pub fn launch_velocity(forward: Vec2, angle: f32, speed: f32) -> Vec2 {
+    speed
+        * Quat::from_rotation_z(dir_to_sign(&forward) * angle)
+            .mul_vec3(forward.extend(0.0))
+            .truncate()
+}
+
+/// Solves for the high arc that lands a projectile at `offset` from the muzzle.
+///
+/// Returns the angle in the same convention as [`launch_velocity`], facing toward the
+/// target, or `None` when the target is out of reach at `speed`. `gravity` is the
+/// downward acceleration in pixels per second squared.
+pub fn solve_launch_angle(offset: Vec2, speed: f32, gravity: f32) -> Option<f32> {
+    let distance = offset.x.abs();
+    let speed_sq = speed * speed;
+    let discriminant =
+        speed_sq * speed_sq - gravity * (gravity * distance * distance + 2.0 * offset.y * speed_sq);
+    if discriminant < 0.0 {
+        return None;
+    }
+    let elevation = f32::atan2(speed_sq + discriminant.sqrt(), gravity * distance);
+    Some(-elevation)
+}
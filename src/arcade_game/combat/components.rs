@@ -1,7 +1,10 @@
 use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
 use bevy_rapier2d::prelude::*;
 
+use crate::arcade_game::character::Team;
 use crate::arcade_game::physics::prelude::*;
+use crate::arcade_game::player::prelude::PlayerId;
 
 #[derive(SystemLabel)]
 pub enum CombatSystem {
@@ -9,6 +12,29 @@ pub enum CombatSystem {
     // Charge,
     Engage,
     Result,
+    Death,
+}
+
+pub struct ProjectileFiredEvent {
+    pub shooter: Entity,
+}
+
+pub struct TileDestroyedEvent {
+    pub translation: Vec3,
+    pub grid_coords: Option<GridCoords>,
+    pub texture_index: Option<u32>,
+}
+
+/// A character ran out of hit points and has been despawned.
+pub struct CharacterDiedEvent {
+    pub entity: Entity,
+    pub team: Team,
+    pub player: Option<PlayerId>,
+}
+
+pub struct ExplosionEvent {
+    pub translation: Vec3,
+    pub radius: f32,
 }
 
 #[derive(Resource, Default)]
@@ -23,11 +49,56 @@ pub struct HitPoint(pub u8);
 #[derive(Component, Default)]
 pub struct Damage(pub u8);
 
+/// Marks an entity that ignores incoming damage.
+// only the dev console's god mode adds it
+#[cfg_attr(not(feature = "dev"), allow(dead_code))]
+#[derive(Component)]
+pub struct Invulnerable;
+
 #[derive(Component, Default)]
 pub struct Projectile {
     pub fx_radius: u8,
+    /// Whose `Damage` the shot deals.
+    pub shooter: Option<Entity>,
+}
+
+/// What a player fires, picked from its inventory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Weapon {
+    #[default]
+    Cannon,
+    /// Clears a wider blast than the cannon.
+    Bazooka,
 }
 
+impl Weapon {
+    pub const ALL: [Weapon; 2] = [Weapon::Cannon, Weapon::Bazooka];
+
+    /// How the weapon is called in the inventory and on the console.
+    pub fn name(self) -> &'static str {
+        match self {
+            Weapon::Cannon => "cannon",
+            Weapon::Bazooka => "bazooka",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|weapon| weapon.name() == name)
+    }
+
+    /// See `Projectile::fx_radius`.
+    pub fn fx_radius(self) -> u8 {
+        match self {
+            Weapon::Cannon => 1,
+            Weapon::Bazooka => 2,
+        }
+    }
+}
+
+/// Whether players' shots can hit other players; they always hit enemies.
+#[derive(Resource, Default)]
+pub struct FriendlyFire(pub bool);
+
 #[derive(Bundle, Default)]
 pub struct ProjectileBundle {
     pub sprite: SpriteSheetBundle,
@@ -40,3 +111,47 @@ pub struct ProjectileBundle {
 impl Projectile {
     pub const DEFAULT_SPEED: f32 = 100.0;
 }
+
+impl ProjectileBundle {
+    pub fn new(
+        sprite_sheet: &SpriteSheetHandle,
+        sprite_index: &ProjectileSpriteIndex,
+        translation: Vec3,
+        velocity: Velocity,
+    ) -> Self {
+        Self {
+            sprite: SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(sprite_index.0),
+                texture_atlas: sprite_sheet.0.clone(),
+                transform: Transform::from_translation(translation).with_scale(0.5 * Vec3::ONE),
+                ..default()
+            },
+            collider: DynamicColliderBundle {
+                collider: Collider::cuboid(4.0, 4.0),
+                rigid_body: RigidBody::Dynamic,
+                locked_axes: LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
+                collision_groups: DynamicColliderBundle::proj_collision_groups(),
+                ..default()
+            },
+            moveable: Moveable::new(Projectile::DEFAULT_SPEED),
+            projectile: Projectile {
+                fx_radius: Weapon::default().fx_radius(),
+                ..default()
+            },
+            velocity,
+        }
+    }
+
+    /// Credits the shot to `shooter` and lets it hit characters in the `targets` groups.
+    pub fn fired_by(mut self, shooter: Entity, targets: Group) -> Self {
+        self.projectile.shooter = Some(shooter);
+        self.collider.collision_groups =
+            DynamicColliderBundle::proj_collision_groups_hitting(targets);
+        self
+    }
+
+    pub fn with_weapon(mut self, weapon: Weapon) -> Self {
+        self.projectile.fx_radius = weapon.fx_radius();
+        self
+    }
+}
@@ -0,0 +1,3 @@
+pub use super::components::{
+    CharacterDiedEvent, ExplosionEvent, ProjectileFiredEvent, TileDestroyedEvent,
+};
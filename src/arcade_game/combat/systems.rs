@@ -1,27 +1,41 @@
+use crate::arcade_game::character::{Character, Team};
+use crate::arcade_game::loading::components::GameAssets;
 use crate::arcade_game::map::*;
 use crate::arcade_game::player::prelude::*;
+use crate::arcade_game::rng::GameRng;
+use crate::arcade_game::tuning::components::Tuning;
+use crate::arcade_game::FIXED_TIMESTEP;
 use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_ecs_tilemap::tiles::TileTextureIndex;
 use bevy_rapier2d::prelude::*;
-use rand;
 use rand::Rng;
 
+use super::ballistics;
 use super::components::*;
 use crate::arcade_game::physics::prelude::*;
 
-fn dir_to_sign(dir: &Vec2) -> f32 {
-    if dir.x > 0.0 {
-        -1.0
-    } else {
-        1.0
-    }
-}
+type TerrainTileQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GlobalTransform,
+        &'static Collider,
+        Option<&'static TileTextureIndex>,
+        Option<&'static GridCoords>,
+    ),
+    With<Terrain>,
+>;
 
 pub fn setup(
     asset_server: Res<AssetServer>,
     mut atlases: ResMut<Assets<TextureAtlas>>,
     mut atlas_handle: ResMut<SpriteSheetHandle>,
+    mut game_assets: ResMut<GameAssets>,
 ) {
     let texture_handle = asset_server.load(super::OBJECT_SPRITE_SHEET_PATH);
+    game_assets.track(&texture_handle);
     let texture_atlas = TextureAtlas::from_grid(
         texture_handle,
         16.0 * Vec2::ONE,
@@ -34,131 +48,232 @@ pub fn setup(
 }
 
 pub fn aim_weapon(
-    kb_input: Res<Input<KeyCode>>,
-    time: Res<Time>,
-    mut player: Query<(&KinematicCharacterController, &mut Player), Without<PlayerChild>>,
+    player_inputs: Res<PlayerInputs>,
+    tuning: Res<Tuning>,
+    mut players: Query<
+        (
+            &PlayerId,
+            &KinematicCharacterController,
+            &mut Player,
+            &Children,
+        ),
+        (Without<PlayerChild>, Without<InputLocked>),
+    >,
     mut child_sprites: Query<&mut Transform, With<PlayerChild>>,
 ) {
-    if player.is_empty() {
-        return;
-    }
-    let (controller, mut player) = player.single_mut();
-    for mut child_transform in &mut child_sprites {
-        let curr_z = child_transform.rotation.to_euler(EulerRot::YXZ).2;
-        // handle aim sprite flip on movement
-        if let Some(translation) = controller.translation {
-            if translation.x != 0.0 {
-                let moved_right = if translation.x > 0.0 { 1.0 } else { 0.0 };
-                child_transform.rotation = Quat::from_euler(
-                    EulerRot::YXZ,
-                    moved_right * std::f32::consts::PI,
-                    0.0,
-                    curr_z,
-                );
+    for (player_id, controller, mut player, children) in &mut players {
+        let player_input = &player_inputs[*player_id];
+        let mut child_sprites = child_sprites.iter_many_mut(children);
+        while let Some(mut child_transform) = child_sprites.fetch_next() {
+            let curr_z = child_transform.rotation.to_euler(EulerRot::YXZ).2;
+            // handle aim sprite flip on movement
+            if let Some(translation) = controller.translation {
+                if translation.x != 0.0 {
+                    let moved_right = if translation.x > 0.0 { 1.0 } else { 0.0 };
+                    child_transform.rotation = Quat::from_euler(
+                        EulerRot::YXZ,
+                        moved_right * std::f32::consts::PI,
+                        0.0,
+                        curr_z,
+                    );
+                }
             }
+            // handle input
+            let mut rot_delta = 0.0;
+            if player_input.aim_up {
+                rot_delta = FIXED_TIMESTEP * f32::to_radians(-tuning.weapon_aim_speed);
+            }
+            if player_input.aim_down {
+                rot_delta = FIXED_TIMESTEP * f32::to_radians(tuning.weapon_aim_speed);
+            }
+            let rot_delta = f32::clamp(
+                rot_delta,
+                -curr_z - f32::to_radians(tuning.weapon_max_angle),
+                -f32::to_radians(tuning.weapon_min_angle) - curr_z,
+            );
+            player.projectile_angle += rot_delta;
+            child_transform.rotate_local_z(rot_delta);
         }
-        // handle input
-        let mut rot_delta = 0.0;
-        if kb_input.pressed(KeyCode::W) || kb_input.pressed(KeyCode::Up) {
-            rot_delta = time.delta_seconds() * f32::to_radians(-PlayerBundle::WEAPON_TRAJEC_ROT);
-        }
-        if kb_input.pressed(KeyCode::S) || kb_input.pressed(KeyCode::Down) {
-            rot_delta = time.delta_seconds() * f32::to_radians(PlayerBundle::WEAPON_TRAJEC_ROT);
-        }
-        let rot_delta = f32::clamp(
-            rot_delta,
-            -curr_z - f32::to_radians(PlayerBundle::WEAPON_TRAJEC_MAX_ROT),
-            -f32::to_radians(PlayerBundle::WEAPON_TRAJEC_MIN_ROT) - curr_z,
-        );
-        player.projectile_angle += rot_delta;
-        child_transform.rotate_local_z(rot_delta);
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn fire_projectile(
-    kb_input: Res<Input<KeyCode>>,
+    mut player_inputs: ResMut<PlayerInputs>,
+    mut game_rng: ResMut<GameRng>,
+    tuning: Res<Tuning>,
+    friendly_fire: Res<FriendlyFire>,
     proj_sprite_sheet: Res<SpriteSheetHandle>,
     proj_sprite_index: Res<ProjectileSpriteIndex>,
-    player: Query<(&GlobalTransform, &Player)>,
+    players: Query<
+        (
+            Entity,
+            &PlayerId,
+            &GlobalTransform,
+            &Player,
+            &CollisionGroups,
+            &Inventory,
+        ),
+        Without<InputLocked>,
+    >,
+    mut ev_fired: EventWriter<ProjectileFiredEvent>,
     mut commands: Commands,
 ) {
-    if kb_input.just_pressed(KeyCode::Space) {
-        let (player_transform, player) = player.single();
-        let projectile_angle = dir_to_sign(&player.forward) * player.projectile_angle;
+    for (player_entity, player_id, player_transform, player, collision_groups, inventory) in
+        &players
+    {
+        if !player_inputs[*player_id].fire {
+            continue;
+        }
+        // shots hit enemies, other players only with friendly fire on, and never the
+        // shooter's own group
+        let mut targets = DynamicColliderBundle::enemy_group();
+        if friendly_fire.0 {
+            targets |= DynamicColliderBundle::all_player_groups();
+        }
+        let targets = targets - collision_groups.memberships;
         commands
-            .spawn(ProjectileBundle {
-                sprite: SpriteSheetBundle {
-                    sprite: TextureAtlasSprite::new(proj_sprite_index.0),
-                    texture_atlas: proj_sprite_sheet.0.clone(),
-                    transform: Transform::from_translation(
-                        player_transform.translation() + 4.0 * player.forward.extend(0.0),
-                    )
-                    .with_scale(0.5 * Vec3::ONE),
-                    ..default()
-                },
-                collider: DynamicColliderBundle {
-                    collider: Collider::cuboid(4.0, 4.0),
-                    rigid_body: RigidBody::Dynamic,
-                    locked_axes: LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
-                    collision_groups: DynamicColliderBundle::proj_collision_groups(),
-                    ..default()
-                },
-                moveable: Moveable::new(Projectile::DEFAULT_SPEED),
-                projectile: Projectile { fx_radius: 1 },
-                velocity: Velocity {
-                    linvel: Projectile::DEFAULT_SPEED
-                        * Quat::from_rotation_z(projectile_angle)
-                            .mul_vec3(player.forward.extend(0.0))
-                            .truncate(),
-                    angvel: rand::thread_rng().gen_range(-30.0..30.0),
-                },
-            })
+            .spawn(
+                ProjectileBundle::new(
+                    &proj_sprite_sheet,
+                    &proj_sprite_index,
+                    player_transform.translation()
+                        + ballistics::MUZZLE_OFFSET * player.forward.extend(0.0),
+                    Velocity {
+                        linvel: ballistics::launch_velocity(
+                            player.forward,
+                            player.projectile_angle,
+                            tuning.projectile_speed,
+                        ),
+                        angvel: game_rng.rng().gen_range(-30.0..30.0),
+                    },
+                )
+                .fired_by(player_entity, targets)
+                .with_weapon(inventory.weapon()),
+            )
             .insert(ActiveEvents::COLLISION_EVENTS);
+        ev_fired.send(ProjectileFiredEvent {
+            shooter: player_entity,
+        });
+    }
+    for player_input in player_inputs.0.iter_mut() {
+        player_input.fire = false;
     }
 }
 
 pub fn deal_projectile_effect(
-    terrain_tiles: Query<(Entity, &GlobalTransform, &Collider), With<Terrain>>,
-    projectiles: Query<(Entity, &Projectile)>,
+    terrain_tiles: TerrainTileQuery,
+    projectiles: Query<(Entity, &GlobalTransform, &Projectile)>,
     mut collision_events: EventReader<CollisionEvent>,
+    mut ev_tile_destroyed: EventWriter<TileDestroyedEvent>,
+    mut ev_explosion: EventWriter<ExplosionEvent>,
     mut commands: Commands,
 ) {
     for collision in collision_events.iter() {
         if let CollisionEvent::Started(entity_one, entity_two, _) = collision {
-            if let Ok((_, tile_transform, tile_collider)) = terrain_tiles.get(*entity_one) {
-                if let Ok((proj_entity, projectile)) = projectiles.get(*entity_two) {
-                    despawn_nearby_tiles(
-                        tile_transform,
-                        tile_collider,
-                        projectile,
-                        &terrain_tiles,
-                        &mut commands,
-                    );
-                    commands.entity(proj_entity).despawn_recursive();
-                }
-            } else if let Ok((_, tile_transform, tile_collider)) = terrain_tiles.get(*entity_two) {
-                if let Ok((proj_entity, projectile)) = projectiles.get(*entity_one) {
-                    despawn_nearby_tiles(
-                        tile_transform,
-                        tile_collider,
-                        projectile,
-                        &terrain_tiles,
-                        &mut commands,
-                    );
-                    commands.entity(proj_entity).despawn_recursive();
+            let (tile_entity, proj_entity) = if terrain_tiles.contains(*entity_one) {
+                (*entity_one, *entity_two)
+            } else {
+                (*entity_two, *entity_one)
+            };
+            if let (
+                Ok((_, tile_transform, tile_collider, _, _)),
+                Ok((_, proj_transform, projectile)),
+            ) = (terrain_tiles.get(tile_entity), projectiles.get(proj_entity))
+            {
+                despawn_nearby_tiles(
+                    tile_transform,
+                    tile_collider,
+                    projectile,
+                    &terrain_tiles,
+                    &mut ev_tile_destroyed,
+                    &mut commands,
+                );
+                ev_explosion.send(ExplosionEvent {
+                    translation: proj_transform.translation(),
+                    radius: projectile.fx_radius as f32 * TILE_SIZE.x,
+                });
+                commands.entity(proj_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Projectiles only reach characters their collision groups allow, see `ProjectileBundle::fired_by`.
+pub fn hit_characters(
+    projectiles: Query<(&GlobalTransform, &Projectile)>,
+    shooters: Query<&Damage>,
+    mut characters: Query<(&mut HitPoint, Option<&Invulnerable>), With<Character>>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut ev_explosion: EventWriter<ExplosionEvent>,
+    mut commands: Commands,
+) {
+    for collision in collision_events.iter() {
+        if let CollisionEvent::Started(entity_one, entity_two, _) = collision {
+            let (character_entity, proj_entity) = if characters.contains(*entity_one) {
+                (*entity_one, *entity_two)
+            } else {
+                (*entity_two, *entity_one)
+            };
+            if let (Ok((mut hit_points, invulnerable)), Ok((proj_transform, projectile))) = (
+                characters.get_mut(character_entity),
+                projectiles.get(proj_entity),
+            ) {
+                let damage = projectile
+                    .shooter
+                    .and_then(|shooter| shooters.get(shooter).ok())
+                    .map(|damage| damage.0)
+                    .unwrap_or_default();
+                if invulnerable.is_none() {
+                    hit_points.0 = hit_points.0.saturating_sub(damage);
                 }
+                ev_explosion.send(ExplosionEvent {
+                    translation: proj_transform.translation(),
+                    radius: projectile.fx_radius as f32 * TILE_SIZE.x,
+                });
+                commands.entity(proj_entity).despawn_recursive();
             }
         }
     }
 }
 
+pub fn remove_dead_characters(
+    characters: Query<(Entity, &HitPoint, &Team, Option<&PlayerId>), Changed<HitPoint>>,
+    mut ev_died: EventWriter<CharacterDiedEvent>,
+    mut commands: Commands,
+) {
+    for (entity, hit_points, team, player_id) in &characters {
+        if hit_points.0 > 0 {
+            continue;
+        }
+        commands.entity(entity).despawn_recursive();
+        ev_died.send(CharacterDiedEvent {
+            entity,
+            team: *team,
+            player: player_id.copied(),
+        });
+    }
+}
+
 fn despawn_nearby_tiles(
     tile_transform: &GlobalTransform,
     tile_collider: &Collider,
     projectile: &Projectile,
-    terrain_tiles: &Query<(Entity, &GlobalTransform, &Collider), With<Terrain>>,
+    terrain_tiles: &TerrainTileQuery,
+    ev_tile_destroyed: &mut EventWriter<TileDestroyedEvent>,
     commands: &mut Commands,
 ) {
+    let mut destroy_tile = |entity: Entity,
+                            g_transform: &GlobalTransform,
+                            texture_index: Option<&TileTextureIndex>,
+                            grid_coords: Option<&GridCoords>| {
+        ev_tile_destroyed.send(TileDestroyedEvent {
+            translation: g_transform.translation(),
+            grid_coords: grid_coords.copied(),
+            texture_index: texture_index.map(|index| index.0),
+        });
+        commands.entity(entity).despawn_recursive();
+    };
     if let Some(collider) = tile_collider.as_cuboid() {
         let fx_area_x = (
             tile_transform.translation().x
@@ -172,7 +287,7 @@ fn despawn_nearby_tiles(
             tile_transform.translation().y
                 + projectile.fx_radius as f32 * collider.half_extents().y,
         );
-        let adjacent_tiles = terrain_tiles.iter().filter(|(_, g_transform, _)| {
+        let adjacent_tiles = terrain_tiles.iter().filter(|(_, g_transform, _, _, _)| {
             let translation_x = g_transform.translation().x;
             let translation_y = g_transform.translation().y;
             translation_x >= fx_area_x.0
@@ -180,15 +295,15 @@ fn despawn_nearby_tiles(
                 && translation_y >= fx_area_y.0
                 && translation_y <= fx_area_y.1
         });
-        for (entity, _, _) in adjacent_tiles {
-            commands.entity(entity).despawn_recursive();
+        for (entity, g_transform, _, texture_index, grid_coords) in adjacent_tiles {
+            destroy_tile(entity, g_transform, texture_index, grid_coords);
         }
     } else {
-        let entity = terrain_tiles
-            .iter()
-            .find(|(_, g_transform, _)| g_transform.translation() == tile_transform.translation());
-        if let Some((entity, _, _)) = entity {
-            commands.entity(entity).despawn_recursive();
+        let entity = terrain_tiles.iter().find(|(_, g_transform, _, _, _)| {
+            g_transform.translation() == tile_transform.translation()
+        });
+        if let Some((entity, g_transform, _, texture_index, grid_coords)) = entity {
+            destroy_tile(entity, g_transform, texture_index, grid_coords);
         }
     }
 }
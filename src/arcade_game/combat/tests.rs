@@ -0,0 +1,135 @@
+use super::ballistics;
+use super::components::{HitPoint, Projectile, Weapon};
+use crate::arcade_game::enemy::prelude::Enemy;
+use crate::arcade_game::harness::{InputAction, TestGame};
+use crate::arcade_game::map::{DestroyedTerrain, Terrain};
+use crate::arcade_game::player::prelude::{Inventory, Player};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+const FLIGHT_FRAMES: usize = 300;
+
+fn terrain_cells(world: &mut World) -> Vec<GridCoords> {
+    world
+        .query_filtered::<&GridCoords, With<Terrain>>()
+        .iter(world)
+        .copied()
+        .collect()
+}
+
+fn destroyed_cells(world: &mut World) -> Vec<GridCoords> {
+    world
+        .resource::<DestroyedTerrain>()
+        .levels
+        .values()
+        .flatten()
+        .copied()
+        .collect()
+}
+
+#[test]
+fn firing_into_terrain_destroys_tiles_around_impact() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    let terrain_before = terrain_cells(game.world());
+
+    game.step(&[InputAction::Press(KeyCode::Space)])
+        .step(&[InputAction::Release(KeyCode::Space)]);
+    let hit = game.run_until(FLIGHT_FRAMES, |world| !destroyed_cells(world).is_empty());
+    assert!(hit, "projectile never hit the terrain");
+    game.run(1);
+
+    let destroyed = destroyed_cells(game.world());
+    let terrain_after = terrain_cells(game.world());
+    assert_eq!(terrain_after.len(), terrain_before.len() - destroyed.len());
+    assert!(destroyed.iter().all(|cell| !terrain_after.contains(cell)));
+
+    // a radius 1 projectile clears at most the 3x3 block around the tile it hit
+    let min_x = destroyed.iter().map(|cell| cell.x).min().unwrap();
+    let max_x = destroyed.iter().map(|cell| cell.x).max().unwrap();
+    let min_y = destroyed.iter().map(|cell| cell.y).min().unwrap();
+    let max_y = destroyed.iter().map(|cell| cell.y).max().unwrap();
+    assert!(max_x - min_x <= 2 && max_y - min_y <= 2);
+}
+
+#[test]
+fn terrain_stays_intact_without_input() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    let terrain_before = terrain_cells(game.world()).len();
+
+    game.run(FLIGHT_FRAMES);
+
+    assert_eq!(terrain_cells(game.world()).len(), terrain_before);
+    assert!(destroyed_cells(game.world()).is_empty());
+}
+
+#[test]
+fn solved_launch_angle_lands_on_target() {
+    let speed = 100.0;
+    let gravity = 98.1;
+    for offset in [
+        Vec2::new(40.0, 0.0),
+        Vec2::new(-60.0, 10.0),
+        Vec2::new(30.0, -20.0),
+    ] {
+        let forward = Vec2::new(offset.x.signum(), 0.0);
+        let angle = ballistics::solve_launch_angle(offset, speed, gravity).unwrap();
+        let velocity = ballistics::launch_velocity(forward, angle, speed);
+        assert!(velocity.y > 0.0, "artillery should lob upwards");
+        let flight_time = offset.x / velocity.x;
+        let height = velocity.y * flight_time - 0.5 * gravity * flight_time * flight_time;
+        assert!(
+            (height - offset.y).abs() < 0.01,
+            "missed {offset} by {}",
+            height - offset.y
+        );
+    }
+}
+
+#[test]
+fn out_of_reach_target_has_no_launch_angle() {
+    assert!(ballistics::solve_launch_angle(Vec2::new(500.0, 0.0), 100.0, 98.1).is_none());
+}
+
+#[test]
+fn characters_without_hit_points_are_removed() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    let world = game.world();
+    let enemy = world.query_filtered::<Entity, With<Enemy>>().single(world);
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.entity_mut(enemy).insert(HitPoint(0));
+
+    game.run(2);
+    let world = game.world();
+    assert!(world.get_entity(enemy).is_none());
+    // only the enemy went, the level was not torn down around it
+    assert!(world.get_entity(player).is_some());
+    assert!(!terrain_cells(world).is_empty());
+}
+
+#[test]
+fn given_weapon_is_fired() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    let world = game.world();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    let mut inventory = Inventory::default();
+    inventory.equip(Weapon::Bazooka);
+    world.entity_mut(player).insert(inventory);
+
+    game.step(&[InputAction::Press(KeyCode::Space)])
+        .step(&[InputAction::Release(KeyCode::Space)]);
+    let fired = game.run_until(FLIGHT_FRAMES, |world| {
+        world.query::<&Projectile>().iter(world).next().is_some()
+    });
+    assert!(fired, "no projectile was fired");
+    let world = game.world();
+    let radii: Vec<u8> = world
+        .query::<&Projectile>()
+        .iter(world)
+        .map(|projectile| projectile.fx_radius)
+        .collect();
+    assert_eq!(radii, vec![Weapon::Bazooka.fx_radius()]);
+}
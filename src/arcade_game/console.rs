@@ -0,0 +1,41 @@
+use crate::arcade_game::GameSystem;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy_egui::{EguiPlugin, EguiSystem};
+use components::*;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// In-game developer console, toggled with the backtick key.
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugin(EguiPlugin);
+        }
+        app.init_resource::<Console>()
+            .add_event::<ConsoleCommandEvent>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                systems::toggle_console
+                    .after(InputSystem)
+                    .after(EguiSystem::ProcessInput)
+                    .before(GameSystem::Input),
+            )
+            .add_system(systems::console_ui.label(ConsoleSystem::Ui))
+            .add_system(systems::apply_player_commands.after(ConsoleSystem::Ui))
+            .add_system(systems::apply_world_commands.after(ConsoleSystem::Ui))
+            .add_system(systems::apply_physics_commands.after(ConsoleSystem::Ui));
+    }
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::Grave;
+const HISTORY_LINES: usize = 100;
+const CONSOLE_WIDTH: f32 = 480.0;
+const SCROLLBACK_HEIGHT: f32 = 240.0;
+const SPAWN_Z: f32 = 10.0;
+const SPAWNABLE: &[&str] = &["projectile", "enemy"];
@@ -0,0 +1,74 @@
+use crate::arcade_game::combat::prelude::Weapon;
+use bevy::prelude::*;
+use std::str::FromStr;
+
+#[derive(SystemLabel)]
+pub enum ConsoleSystem {
+    Ui,
+}
+
+/// Scrollback, the line being typed and whether the console is shown.
+#[derive(Resource, Default)]
+pub struct Console {
+    pub(crate) open: bool,
+    pub(crate) input: String,
+    pub(crate) history: Vec<String>,
+}
+
+impl Console {
+    pub fn log(&mut self, line: impl Into<String>) {
+        self.history.push(line.into());
+        if self.history.len() > super::HISTORY_LINES {
+            self.history.remove(0);
+        }
+    }
+}
+
+pub struct ConsoleCommandEvent(pub ConsoleCommand);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleCommand {
+    Level(usize),
+    God,
+    Give(Weapon),
+    Spawn { entity: String, position: Vec2 },
+    Hp(u8),
+    NoClip,
+    PhysicsDebug(bool),
+}
+
+impl FromStr for ConsoleCommand {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["level", index] => index
+                .parse()
+                .map(ConsoleCommand::Level)
+                .map_err(|_| format!("not a level index: {}", index)),
+            ["god"] => Ok(ConsoleCommand::God),
+            ["give", name] => Weapon::from_name(name)
+                .map(ConsoleCommand::Give)
+                .ok_or_else(|| {
+                    let names: Vec<_> = Weapon::ALL.iter().map(|weapon| weapon.name()).collect();
+                    format!("unknown weapon: {}, try one of: {}", name, names.join(", "))
+                }),
+            ["spawn", entity, x, y] => match (x.parse(), y.parse()) {
+                (Ok(x), Ok(y)) => Ok(ConsoleCommand::Spawn {
+                    entity: entity.to_string(),
+                    position: Vec2::new(x, y),
+                }),
+                _ => Err(format!("not a position: {} {}", x, y)),
+            },
+            ["hp", hit_points] => hit_points
+                .parse()
+                .map(ConsoleCommand::Hp)
+                .map_err(|_| format!("not a hit point value: {}", hit_points)),
+            ["noclip"] => Ok(ConsoleCommand::NoClip),
+            ["physics", "debug", "on"] => Ok(ConsoleCommand::PhysicsDebug(true)),
+            ["physics", "debug", "off"] => Ok(ConsoleCommand::PhysicsDebug(false)),
+            _ => Err(format!("unknown command: {}", line)),
+        }
+    }
+}
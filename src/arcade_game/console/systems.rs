@@ -0,0 +1,241 @@
+use crate::arcade_game::combat::prelude::*;
+use crate::arcade_game::enemy::prelude::EnemyBundle;
+use crate::arcade_game::player::prelude::*;
+use crate::arcade_game::MapLevel;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::render::DebugRenderContext;
+
+use super::components::*;
+
+type ConsolePlayerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut HitPoint,
+        &'static mut Inventory,
+        &'static mut CollisionGroups,
+        &'static mut GravityScale,
+        &'static mut Velocity,
+        &'static mut KinematicCharacterController,
+        Option<&'static Invulnerable>,
+        Option<&'static NoClip>,
+        &'static PlayerId,
+    ),
+    With<Player>,
+>;
+
+pub fn toggle_console(mut kb_input: ResMut<Input<KeyCode>>, mut console: ResMut<Console>) {
+    if kb_input.just_pressed(super::TOGGLE_KEY) {
+        console.open = !console.open;
+    }
+    if console.open {
+        // typing into the console must not move the player or open the pause menu
+        kb_input.reset_all();
+    }
+}
+
+pub fn console_ui(
+    mut egui_context: ResMut<EguiContext>,
+    mut console: ResMut<Console>,
+    mut ev_command: EventWriter<ConsoleCommandEvent>,
+) {
+    if !console.open {
+        return;
+    }
+    let console = &mut *console;
+    egui::Window::new("Console")
+        .default_width(super::CONSOLE_WIDTH)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(super::SCROLLBACK_HEIGHT)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in &console.history {
+                        ui.monospace(line);
+                    }
+                });
+            // the toggle key ends up in the text field as well
+            console.input.retain(|c| c != '`');
+            let response = ui.text_edit_singleline(&mut console.input);
+            if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim();
+                if !line.is_empty() {
+                    console.log(format!("> {}", line));
+                    match line.parse() {
+                        Ok(command) => ev_command.send(ConsoleCommandEvent(command)),
+                        Err(err) => console.log(err),
+                    }
+                }
+            }
+            response.request_focus();
+        });
+}
+
+pub fn apply_player_commands(
+    mut ev_command: EventReader<ConsoleCommandEvent>,
+    mut console: ResMut<Console>,
+    mut player: ConsolePlayerQuery,
+    mut commands: Commands,
+) {
+    for ConsoleCommandEvent(command) in ev_command.iter() {
+        if !matches!(
+            command,
+            ConsoleCommand::God
+                | ConsoleCommand::Give(_)
+                | ConsoleCommand::Hp(_)
+                | ConsoleCommand::NoClip
+        ) {
+            continue;
+        }
+        let (
+            entity,
+            mut hit_points,
+            mut inventory,
+            mut collision_groups,
+            mut gravity_scale,
+            mut velocity,
+            mut controller,
+            invulnerable,
+            noclip,
+            _,
+        ) = match player
+            .iter_mut()
+            .find(|(.., player_id)| **player_id == PlayerId::ONE)
+        {
+            Some(player) => player,
+            None => {
+                console.log("no player in the game");
+                continue;
+            }
+        };
+        match command {
+            ConsoleCommand::God => {
+                if invulnerable.is_some() {
+                    commands.entity(entity).remove::<Invulnerable>();
+                    console.log("god mode off");
+                } else {
+                    commands.entity(entity).insert(Invulnerable);
+                    console.log("god mode on");
+                }
+            }
+            ConsoleCommand::Give(weapon) => {
+                inventory.equip(*weapon);
+                console.log(format!("gave {}, firing it from now on", weapon.name()));
+            }
+            ConsoleCommand::Hp(value) => {
+                hit_points.0 = *value;
+                console.log(format!("hit points set to {}", value));
+            }
+            ConsoleCommand::NoClip => match noclip {
+                Some(noclip) => {
+                    *collision_groups = noclip.collision_groups;
+                    *gravity_scale = GravityScale(noclip.gravity_scale.0);
+                    controller.filter_groups = None;
+                    commands.entity(entity).remove::<NoClip>();
+                    console.log("noclip off");
+                }
+                None => {
+                    commands.entity(entity).insert(NoClip {
+                        collision_groups: *collision_groups,
+                        gravity_scale: GravityScale(gravity_scale.0),
+                    });
+                    *collision_groups = CollisionGroups::new(Group::NONE, Group::NONE);
+                    gravity_scale.0 = 0.0;
+                    velocity.linvel = Vec2::ZERO;
+                    controller.filter_groups = Some(CollisionGroups::new(Group::NONE, Group::NONE));
+                    console.log("noclip on");
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn apply_world_commands(
+    mut ev_command: EventReader<ConsoleCommandEvent>,
+    mut console: ResMut<Console>,
+    ldtk_worlds: Query<&Handle<LdtkAsset>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    sprite_sheet: Res<SpriteSheetHandle>,
+    sprite_index: Res<ProjectileSpriteIndex>,
+    map_level: Res<MapLevel>,
+    mut commands: Commands,
+) {
+    for ConsoleCommandEvent(command) in ev_command.iter() {
+        match command {
+            ConsoleCommand::Level(index) => {
+                let level_count = ldtk_worlds
+                    .iter()
+                    .find_map(|handle| ldtk_assets.get(handle))
+                    .map(|ldtk_asset| ldtk_asset.project.levels.len());
+                match level_count {
+                    Some(count) if *index < count => {
+                        commands.insert_resource(LevelSelection::Index(*index));
+                        console.log(format!("switching to level {}", index));
+                    }
+                    Some(count) => console.log(format!("the map only has {} levels", count)),
+                    None => console.log("no map loaded"),
+                }
+            }
+            ConsoleCommand::Spawn { entity, position } => match entity.as_str() {
+                "projectile" => {
+                    commands
+                        .spawn(ProjectileBundle::new(
+                            &sprite_sheet,
+                            &sprite_index,
+                            position.extend(super::SPAWN_Z),
+                            Velocity::zero(),
+                        ))
+                        .insert(ActiveEvents::COLLISION_EVENTS);
+                    console.log(format!("spawned {} at {}", entity, position));
+                }
+                // parented to the map so it goes away with the level
+                "enemy" => match map_level.entity {
+                    Some(map) => {
+                        commands.entity(map).with_children(|map| {
+                            map.spawn(EnemyBundle::new(
+                                &sprite_sheet,
+                                position.extend(super::SPAWN_Z),
+                            ));
+                        });
+                        console.log(format!("spawned {} at {}", entity, position));
+                    }
+                    None => console.log("no map loaded"),
+                },
+                _ => console.log(format!(
+                    "cannot spawn {}, try one of: {}",
+                    entity,
+                    super::SPAWNABLE.join(", ")
+                )),
+            },
+            _ => {}
+        }
+    }
+}
+
+pub fn apply_physics_commands(
+    mut ev_command: EventReader<ConsoleCommandEvent>,
+    mut console: ResMut<Console>,
+    mut debug_render: Option<ResMut<DebugRenderContext>>,
+) {
+    for ConsoleCommandEvent(command) in ev_command.iter() {
+        if let ConsoleCommand::PhysicsDebug(enabled) = command {
+            match debug_render.as_mut() {
+                Some(debug_render) => {
+                    debug_render.enabled = *enabled;
+                    console.log(format!(
+                        "collider rendering {}",
+                        if *enabled { "on" } else { "off" }
+                    ));
+                }
+                None => console.log("collider rendering is not available"),
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use super::components::ConsoleCommand;
+use crate::arcade_game::combat::prelude::Weapon;
+
+#[test]
+fn parses_commands_with_arguments() {
+    assert_eq!("level 2".parse(), Ok(ConsoleCommand::Level(2)));
+    assert_eq!("  god ".parse(), Ok(ConsoleCommand::God));
+    assert_eq!(
+        "give bazooka".parse(),
+        Ok(ConsoleCommand::Give(Weapon::Bazooka))
+    );
+    assert_eq!(
+        "spawn projectile 12 -4.5".parse(),
+        Ok(ConsoleCommand::Spawn {
+            entity: "projectile".to_string(),
+            position: Vec2::new(12.0, -4.5),
+        })
+    );
+    assert_eq!("hp 50".parse(), Ok(ConsoleCommand::Hp(50)));
+    assert_eq!("noclip".parse(), Ok(ConsoleCommand::NoClip));
+    assert_eq!(
+        "physics debug off".parse(),
+        Ok(ConsoleCommand::PhysicsDebug(false))
+    );
+}
+
+#[test]
+fn rejects_malformed_commands() {
+    assert!("level".parse::<ConsoleCommand>().is_err());
+    assert!("hp 300".parse::<ConsoleCommand>().is_err());
+    assert!("spawn projectile x 1".parse::<ConsoleCommand>().is_err());
+    assert!("physics debug maybe".parse::<ConsoleCommand>().is_err());
+    assert!("give spoon".parse::<ConsoleCommand>().is_err());
+    assert!("fly".parse::<ConsoleCommand>().is_err());
+}
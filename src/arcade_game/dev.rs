@@ -0,0 +1,70 @@
+use crate::arcade_game::character::{Character, Team};
+use crate::arcade_game::physics::prelude::Moveable;
+use bevy::diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::log::Level;
+use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
+use bevy_inspector_egui::DefaultInspectorConfigPlugin;
+use components::*;
+use timing::SystemTimingLayer;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+pub mod components;
+mod systems;
+mod timing;
+
+/// Debug views that only exist in builds with the `dev` feature.
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugin(EguiPlugin);
+        }
+        app.add_plugin(DefaultInspectorConfigPlugin)
+            .add_plugin(FrameTimeDiagnosticsPlugin)
+            .add_plugin(EntityCountDiagnosticsPlugin)
+            .register_type::<Moveable>()
+            .register_type::<Character>()
+            .register_type::<Team>()
+            .init_resource::<DevTools>()
+            // left empty unless `DevLogPlugin` measures the systems
+            .init_resource::<SystemTimings>()
+            .add_system(systems::handle_dev_hotkeys)
+            .add_system(systems::world_inspector)
+            .add_system(systems::performance_overlay)
+            .add_system(systems::system_timing_window);
+    }
+}
+
+/// Takes the place of Bevy's `LogPlugin` in dev builds. Logs the same way, and also times
+/// every system from the spans Bevy opens around them with its `trace` feature.
+pub struct DevLogPlugin {
+    pub filter: String,
+    pub level: Level,
+}
+
+impl Plugin for DevLogPlugin {
+    fn build(&self, app: &mut App) {
+        let timings = SystemTimings::default();
+        let filter = EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new(format!("{},{}", self.level, self.filter)))
+            .unwrap_or_else(|err| {
+                eprintln!("invalid log filter {}: {}", self.filter, err);
+                EnvFilter::new(self.level.to_string())
+            });
+        // only the log output is filtered, the timing layer has to see every system span
+        tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().with_filter(filter))
+            .with(SystemTimingLayer(timings.clone()))
+            .init();
+        app.insert_resource(timings);
+    }
+}
+
+const TIMING_SMOOTHING: f32 = 0.1;
+/// How many of the slowest systems the timing window lists.
+const TIMING_ROWS: usize = 24;
+const INSPECTOR_SIZE: [f32; 2] = [320.0, 480.0];
+const OVERLAY_OFFSET: [f32; 2] = [8.0, 8.0];
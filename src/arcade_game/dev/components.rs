@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Which debug views are currently shown.
+#[derive(Resource, Default)]
+pub struct DevTools {
+    pub(crate) inspector: bool,
+    pub(crate) overlay: bool,
+    pub(crate) timings: bool,
+}
+
+/// Smoothed wall time of a single run of each system, filled in by the tracing layer
+/// that measures them, which holds a clone.
+#[derive(Resource, Clone, Default)]
+pub struct SystemTimings(Arc<Mutex<HashMap<String, Duration>>>);
+
+impl SystemTimings {
+    pub fn record(&self, system: &str, elapsed: Duration) {
+        let mut averages = self.0.lock().unwrap();
+        match averages.get_mut(system) {
+            Some(average) => {
+                *average = average.mul_f32(1.0 - super::TIMING_SMOOTHING)
+                    + elapsed.mul_f32(super::TIMING_SMOOTHING);
+            }
+            None => {
+                averages.insert(system.to_string(), elapsed);
+            }
+        }
+    }
+
+    /// The `count` slowest systems, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<(String, Duration)> {
+        let mut averages: Vec<_> = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(system, average)| (system.clone(), *average))
+            .collect();
+        averages.sort_by(|a, b| b.1.cmp(&a.1));
+        averages.truncate(count);
+        averages
+    }
+}
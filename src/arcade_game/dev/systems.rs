@@ -0,0 +1,86 @@
+use bevy::diagnostic::{Diagnostics, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use bevy_inspector_egui::bevy_inspector;
+use bevy_rapier2d::render::DebugRenderContext;
+
+use super::components::*;
+
+/// `F1` world inspector, `F2` collider rendering, `F3` FPS overlay, `F4` system timing.
+pub fn handle_dev_hotkeys(
+    kb_input: Res<Input<KeyCode>>,
+    mut dev_tools: ResMut<DevTools>,
+    debug_render: Option<ResMut<DebugRenderContext>>,
+) {
+    if kb_input.just_pressed(KeyCode::F1) {
+        dev_tools.inspector = !dev_tools.inspector;
+    }
+    if kb_input.just_pressed(KeyCode::F2) {
+        if let Some(mut debug_render) = debug_render {
+            debug_render.enabled = !debug_render.enabled;
+        }
+    }
+    if kb_input.just_pressed(KeyCode::F3) {
+        dev_tools.overlay = !dev_tools.overlay;
+    }
+    if kb_input.just_pressed(KeyCode::F4) {
+        dev_tools.timings = !dev_tools.timings;
+    }
+}
+
+pub fn world_inspector(world: &mut World) {
+    if !world.resource::<DevTools>().inspector {
+        return;
+    }
+    let egui_context = world.resource_mut::<EguiContext>().ctx_mut().clone();
+    egui::Window::new("World")
+        .default_size(super::INSPECTOR_SIZE)
+        .show(&egui_context, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                bevy_inspector::ui_for_world(world, ui);
+            });
+        });
+}
+
+pub fn performance_overlay(
+    mut egui_context: ResMut<EguiContext>,
+    dev_tools: Res<DevTools>,
+    diagnostics: Res<Diagnostics>,
+) {
+    if !dev_tools.overlay {
+        return;
+    }
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let entities = diagnostics
+        .get(EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+        .and_then(|count| count.value())
+        .unwrap_or_default();
+    egui::Area::new("performance_overlay")
+        .anchor(egui::Align2::LEFT_TOP, super::OVERLAY_OFFSET)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.monospace(format!("{:>4.0} fps", fps));
+            ui.monospace(format!("{:>4} entities", entities));
+        });
+}
+
+pub fn system_timing_window(
+    mut egui_context: ResMut<EguiContext>,
+    dev_tools: Res<DevTools>,
+    timings: Res<SystemTimings>,
+) {
+    if !dev_tools.timings {
+        return;
+    }
+    egui::Window::new("System timing").show(egui_context.ctx_mut(), |ui| {
+        for (system, average) in timings.slowest(super::TIMING_ROWS) {
+            ui.monospace(format!(
+                "{:>7.3} ms  {}",
+                average.as_secs_f64() * 1000.0,
+                system
+            ));
+        }
+    });
+}
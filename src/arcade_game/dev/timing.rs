@@ -0,0 +1,74 @@
+use bevy::utils::get_short_name;
+use bevy::utils::tracing::field::{Field, Visit};
+use bevy::utils::tracing::span::{Attributes, Id};
+use bevy::utils::tracing::Subscriber;
+use std::fmt;
+use std::time::Instant;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::components::SystemTimings;
+
+/// Spans Bevy opens around every run of a system.
+const SYSTEM_SPANS: [&str; 2] = ["system", "exclusive_system"];
+
+/// Measures how long each system span is entered and records it in `SystemTimings`.
+pub struct SystemTimingLayer(pub SystemTimings);
+
+/// Kept on a system span until it is exited.
+struct SystemSpan {
+    name: String,
+    entered: Option<Instant>,
+}
+
+/// Picks the system name out of a span's fields.
+#[derive(Default)]
+struct NameVisitor(Option<String>);
+
+impl Visit for NameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(get_short_name(value));
+        }
+    }
+
+    // Bevy records the name as a string, other fields are of no use here
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+impl<S> Layer<S> for SystemTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !SYSTEM_SPANS.contains(&attrs.metadata().name()) {
+            return;
+        }
+        let mut visitor = NameVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(name), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(SystemSpan {
+                name,
+                entered: None,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(system) = span.extensions_mut().get_mut::<SystemSpan>() {
+                system.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(system) = span.extensions_mut().get_mut::<SystemSpan>() {
+                if let Some(entered) = system.entered.take() {
+                    self.0.record(&system.name, entered.elapsed());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::arcade_game::ai::prelude::AiBrain;
+use crate::arcade_game::character::*;
+use crate::arcade_game::combat::prelude::SpriteSheetHandle;
+use crate::arcade_game::ldtk;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use std::str::FromStr;
+
+#[derive(Component, Default)]
+pub struct Enemy;
+
+/// Which brain an enemy runs, picked with the `Behavior` field in LDtk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AiBehavior {
+    #[default]
+    Idle,
+    Patrol,
+    Chase,
+    Artillery,
+}
+
+impl FromStr for AiBehavior {
+    type Err = String;
+
+    fn from_str(behavior: &str) -> Result<Self, Self::Err> {
+        match behavior {
+            "Idle" => Ok(AiBehavior::Idle),
+            "Patrol" => Ok(AiBehavior::Patrol),
+            "Chase" => Ok(AiBehavior::Chase),
+            "Artillery" => Ok(AiBehavior::Artillery),
+            _ => Err(format!("unknown behavior {}", behavior)),
+        }
+    }
+}
+
+/// AI settings authored on the LDtk entity. Ranges are in pixels, the cooldown in seconds.
+#[derive(Component, Debug, Clone)]
+pub struct AiParams {
+    pub behavior: AiBehavior,
+    pub sight_range: f32,
+    pub attack_range: f32,
+    pub attack_cooldown: f32,
+}
+
+impl AiParams {
+    pub const DEFAULT_SIGHT_RANGE: f32 = 96.0;
+    pub const DEFAULT_ATTACK_RANGE: f32 = 64.0;
+    pub const DEFAULT_ATTACK_COOLDOWN: f32 = 2.0;
+}
+
+impl Default for AiParams {
+    fn default() -> Self {
+        Self {
+            behavior: AiBehavior::default(),
+            sight_range: Self::DEFAULT_SIGHT_RANGE,
+            attack_range: Self::DEFAULT_ATTACK_RANGE,
+            attack_cooldown: Self::DEFAULT_ATTACK_COOLDOWN,
+        }
+    }
+}
+
+impl From<EntityInstance> for AiParams {
+    fn from(entity_instance: EntityInstance) -> Self {
+        let behavior = match ldtk::string_field(&entity_instance, ldtk::BEHAVIOR_FIELD) {
+            Some(behavior) => behavior.parse().unwrap_or_else(|err| {
+                warn!(
+                    "{} on {}: {}",
+                    ldtk::BEHAVIOR_FIELD,
+                    entity_instance.iid,
+                    err
+                );
+                AiBehavior::default()
+            }),
+            None => AiBehavior::default(),
+        };
+        let number = |identifier: &str, default: f32| {
+            ldtk::number_field(&entity_instance, identifier).unwrap_or(default)
+        };
+        Self {
+            behavior,
+            sight_range: number(ldtk::SIGHT_RANGE_FIELD, Self::DEFAULT_SIGHT_RANGE),
+            attack_range: number(ldtk::ATTACK_RANGE_FIELD, Self::DEFAULT_ATTACK_RANGE),
+            attack_cooldown: number(ldtk::ATTACK_COOLDOWN_FIELD, Self::DEFAULT_ATTACK_COOLDOWN),
+        }
+    }
+}
+
+#[derive(Bundle, LdtkEntity)]
+pub struct EnemyBundle {
+    #[sprite_sheet_bundle]
+    #[bundle]
+    sprite_bundle: SpriteSheetBundle,
+    #[from_entity_instance]
+    #[bundle]
+    character_bundle: CharacterBundle,
+    #[from_entity_instance]
+    ai_params: AiParams,
+    brain: AiBrain,
+    enemy: Enemy,
+    name: Name,
+}
+
+impl Default for EnemyBundle {
+    fn default() -> Self {
+        Self {
+            sprite_bundle: SpriteSheetBundle::default(),
+            character_bundle: CharacterBundle::default(),
+            ai_params: AiParams::default(),
+            brain: AiBrain::default(),
+            enemy: Enemy,
+            name: Name::from(Self::DEFAULT_NAME),
+        }
+    }
+}
+
+impl EnemyBundle {
+    pub const DEFAULT_NAME: &str = "Enemy";
+    /// The enemy tile of the object sprite sheet, the one it has in LDtk.
+    pub const SPRITE_INDEX: usize = 31;
+
+    /// An enemy that is not placed in LDtk, e.g. spawned from the console. It gets the
+    /// values an authored enemy gets for fields left empty.
+    pub fn new(sprite_sheet: &SpriteSheetHandle, translation: Vec3) -> Self {
+        let entity_instance = EntityInstance {
+            identifier: ldtk::ENEMY_ID.to_string(),
+            ..default()
+        };
+        Self {
+            sprite_bundle: SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(Self::SPRITE_INDEX),
+                texture_atlas: sprite_sheet.0.clone(),
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+            character_bundle: entity_instance.into(),
+            ..default()
+        }
+    }
+}
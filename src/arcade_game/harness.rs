@@ -0,0 +1,162 @@
+use crate::arcade_game::map::Terrain;
+use crate::arcade_game::player::prelude::Player;
+use crate::arcade_game::replay::components::{Replay, ReplayPlayback};
+use crate::arcade_game::rng::GameRng;
+use crate::arcade_game::{ArcadeGame, GameState, SceneState, FIXED_TIMESTEP};
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::asset::AssetPlugin;
+use bevy::hierarchy::HierarchyPlugin;
+use bevy::prelude::*;
+use bevy::time::TimePlugin;
+use bevy::transform::TransformPlugin;
+use bevy_ecs_ldtk::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub enum InputAction {
+    Press(KeyCode),
+    Release(KeyCode),
+}
+
+/// Runs [`ArcadeGame`] without a window, advancing time and physics by a fixed step
+/// per frame so tests see the same simulation on every run.
+pub struct TestGame {
+    pub app: App,
+    now: Instant,
+}
+
+impl TestGame {
+    pub const FRAME_TIME: f32 = FIXED_TIMESTEP;
+    pub const SEED: u64 = 0;
+    pub const LOAD_FRAMES: usize = 5000;
+    pub const LANDING_FRAMES: usize = 120;
+
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(
+            MinimalPlugins
+                .build()
+                .disable::<TimePlugin>()
+                .disable::<ScheduleRunnerPlugin>(),
+        )
+        .add_plugin(AssetPlugin {
+            asset_folder: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_string(),
+            watch_for_changes: false,
+        })
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(ImagePlugin::default_nearest())
+        .add_asset::<Shader>()
+        .add_asset::<Mesh>()
+        .add_asset::<ColorMaterial>()
+        .add_asset::<TextureAtlas>()
+        .init_resource::<Input<KeyCode>>()
+        .insert_resource(Time::default())
+        .add_plugin(ArcadeGame {
+            headless: true,
+            ..default()
+        })
+        .insert_resource(GameRng::from_seed(Self::SEED));
+
+        let now = app.world.resource::<Time>().startup();
+        Self { app, now }
+    }
+
+    /// Leaves the main menu and spawns the map, like pressing "Play".
+    pub fn start(&mut self) -> &mut Self {
+        self.app.world.insert_resource(NextState(GameState::InGame));
+        self.app
+            .world
+            .insert_resource(NextState(SceneState::Loading));
+        self
+    }
+
+    /// Starts `replay` on its level, seed and starting state, with its input replacing the
+    /// keyboard.
+    pub fn play(&mut self, replay: Replay) -> &mut Self {
+        let (destroyed_terrain, pending_load) = replay.start_state();
+        self.app.world.insert_resource(destroyed_terrain);
+        self.app.world.insert_resource(pending_load);
+        self.app
+            .world
+            .insert_resource(LevelSelection::Identifier(replay.level.clone()));
+        self.app
+            .world
+            .insert_resource(GameRng::from_seed(replay.seed));
+        self.app.world.insert_resource(ReplayPlayback::new(replay));
+        self.start()
+    }
+
+    /// Runs until the player and the terrain have spawned and the player has landed.
+    pub fn wait_for_level(&mut self) -> &mut Self {
+        let loaded = self.run_until(Self::LOAD_FRAMES, |world| {
+            let has_player = world
+                .query_filtered::<(), With<Player>>()
+                .iter(world)
+                .next()
+                .is_some();
+            let has_terrain = world
+                .query_filtered::<(), With<Terrain>>()
+                .iter(world)
+                .next()
+                .is_some();
+            has_player && has_terrain
+        });
+        assert!(
+            loaded,
+            "level did not spawn within {} frames",
+            Self::LOAD_FRAMES
+        );
+        self.run(Self::LANDING_FRAMES)
+    }
+
+    /// Applies `actions` to the keyboard state, then runs a single frame.
+    pub fn step(&mut self, actions: &[InputAction]) -> &mut Self {
+        {
+            let mut input = self.app.world.resource_mut::<Input<KeyCode>>();
+            for action in actions {
+                match *action {
+                    InputAction::Press(key) => input.press(key),
+                    InputAction::Release(key) => input.release(key),
+                }
+            }
+        }
+        self.now += Duration::from_secs_f32(Self::FRAME_TIME);
+        let now = self.now;
+        self.app
+            .world
+            .resource_mut::<Time>()
+            .update_with_instant(now);
+        self.app.update();
+        // keys stay held between frames, only the edge flags are reset
+        self.app.world.resource_mut::<Input<KeyCode>>().clear();
+        self
+    }
+
+    pub fn run(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.step(&[]);
+        }
+        self
+    }
+
+    /// Steps until `condition` holds, returning `false` if `max_frames` ran out first.
+    pub fn run_until(
+        &mut self,
+        max_frames: usize,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> bool {
+        for _ in 0..max_frames {
+            if condition(&mut self.app.world) {
+                return true;
+            }
+            self.step(&[]);
+        }
+        condition(&mut self.app.world)
+    }
+
+    pub fn world(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+}
@@ -1,12 +1,53 @@
+use crate::arcade_game::combat::events::TileDestroyedEvent;
+use crate::arcade_game::enemy::prelude::EnemyBundle;
+use crate::arcade_game::lighting::components::LightBundle;
+use crate::arcade_game::loading::components::GameAssets;
 use crate::arcade_game::map::*;
 use crate::arcade_game::player::prelude::PlayerBundle;
 use crate::arcade_game::MapLevel;
 use bevy::prelude::*;
+use bevy_ecs_ldtk::ldtk::{FieldInstance, Level};
 use bevy_ecs_ldtk::prelude::*;
 
 pub const MAP_PATH: &str = "map.ldtk";
 
 pub const PLAYER_ID: &str = "Player";
+pub const ENEMY_ID: &str = "Enemy";
+pub const LIGHT_ID: &str = "Light";
+
+pub const IDLE_FRAMES_FIELD: &str = "IdleFrames";
+pub const RUN_FRAMES_FIELD: &str = "RunFrames";
+pub const JUMP_FRAMES_FIELD: &str = "JumpFrames";
+pub const SHOOT_FRAMES_FIELD: &str = "ShootFrames";
+pub const FRAME_TIME_FIELD: &str = "FrameTime";
+
+// Optional character fields; missing ones fall back to the defaults in code.
+// The player's speed comes from the tuning file unless `MoveSpeed` is set.
+pub const HIT_POINTS_FIELD: &str = "HitPoints";
+pub const DAMAGE_FIELD: &str = "Damage";
+pub const MOVE_SPEED_FIELD: &str = "MoveSpeed";
+pub const COLLIDER_WIDTH_FIELD: &str = "ColliderWidth";
+pub const COLLIDER_HEIGHT_FIELD: &str = "ColliderHeight";
+pub const TEAM_FIELD: &str = "Team";
+
+// Optional AI fields on enemies
+pub const BEHAVIOR_FIELD: &str = "Behavior";
+pub const SIGHT_RANGE_FIELD: &str = "SightRange";
+pub const ATTACK_RANGE_FIELD: &str = "AttackRange";
+pub const ATTACK_COOLDOWN_FIELD: &str = "AttackCooldown";
+
+// Optional light fields, e.g. for torches
+pub const RADIUS_FIELD: &str = "Radius";
+pub const INTENSITY_FIELD: &str = "Intensity";
+pub const FLICKER_FIELD: &str = "Flicker";
+
+// Optional level fields for the parallax background
+pub const PARALLAX_IMAGES_FIELD: &str = "ParallaxImages";
+pub const PARALLAX_FACTORS_FIELD: &str = "ParallaxFactors";
+
+/// Optional level field: how bright the level is without lights, from 0 for pitch black to
+/// 1 for fully lit.
+pub const AMBIENT_LIGHT_FIELD: &str = "AmbientLight";
 
 pub const TERRAIN: i32 = 1;
 pub const UPHILL_TERRAIN: i32 = 8;
@@ -24,6 +65,11 @@ impl Plugin for LdtkMapBackendPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(LdtkPlugin)
             .insert_resource(LevelSelection::Index(0))
+            .init_resource::<StartLevel>()
+            .init_resource::<MapPath>()
+            .init_resource::<DestroyedTerrain>()
+            .add_system(record_destroyed_terrain)
+            .add_system(restore_destroyed_terrain)
             .register_ldtk_int_cell::<TerrainBundle>(TERRAIN)
             .register_ldtk_int_cell::<UphillTerrainBundle>(UPHILL_TERRAIN)
             .register_ldtk_int_cell::<DownhillTerrainBundle>(DOWNHILL_TERRAIN)
@@ -33,18 +79,44 @@ impl Plugin for LdtkMapBackendPlugin {
             .register_ldtk_int_cell::<BackgroundBundle>(TILE_ON_TOP)
             .register_ldtk_int_cell::<BackgroundBundle>(PLANT_FOOT)
             .register_ldtk_int_cell::<BackgroundBundle>(CHAIN)
-            .register_ldtk_entity::<PlayerBundle>(PLAYER_ID);
+            .register_ldtk_entity::<PlayerBundle>(PLAYER_ID)
+            .register_ldtk_entity::<EnemyBundle>(ENEMY_ID)
+            .register_ldtk_entity::<LightBundle>(LIGHT_ID);
+    }
+}
+
+/// LDtk project the scene is built from, relative to the assets folder.
+#[derive(Resource)]
+pub struct MapPath(pub String);
+
+impl Default for MapPath {
+    fn default() -> Self {
+        Self(MAP_PATH.to_string())
+    }
+}
+
+/// Level a new game starts on, the first one unless `--level` picks another.
+#[derive(Resource, Clone)]
+pub struct StartLevel(pub LevelSelection);
+
+impl Default for StartLevel {
+    fn default() -> Self {
+        Self(LevelSelection::Index(0))
     }
 }
 
 pub fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    map_path: Res<MapPath>,
     mut map_level: ResMut<MapLevel>,
+    mut game_assets: ResMut<GameAssets>,
 ) {
+    let ldtk_handle = asset_server.load(map_path.0.as_str());
+    game_assets.track(&ldtk_handle);
     let level_entity = commands
         .spawn(LdtkWorldBundle {
-            ldtk_handle: asset_server.load(MAP_PATH),
+            ldtk_handle,
             transform: Transform::from_xyz(-128.0, -128.0, 0.0),
             ..default()
         })
@@ -59,3 +131,166 @@ pub fn cleanup(mut commands: Commands, mut map_level: ResMut<MapLevel>) {
         map_level.entity = None;
     }
 }
+
+/// Modes other than arcade start every scene with an intact map.
+pub fn reset_destroyed_terrain(mut destroyed_terrain: ResMut<DestroyedTerrain>) {
+    *destroyed_terrain = DestroyedTerrain::default();
+}
+
+pub fn current_level_identifier(
+    level_selection: &LevelSelection,
+    ldtk_worlds: &Query<&Handle<LdtkAsset>>,
+    ldtk_assets: &Assets<LdtkAsset>,
+) -> Option<String> {
+    ldtk_worlds
+        .iter()
+        .find_map(|handle| ldtk_assets.get(handle))
+        .and_then(|ldtk_asset| ldtk_asset.get_level(level_selection))
+        .map(|level| level.identifier.clone())
+}
+
+fn find_field<'a>(fields: &'a [FieldInstance], identifier: &str) -> Option<&'a FieldValue> {
+    fields
+        .iter()
+        .find(|field| field.identifier == identifier)
+        .map(|field| &field.value)
+}
+
+fn field<'a>(entity_instance: &'a EntityInstance, identifier: &str) -> Option<&'a FieldValue> {
+    find_field(&entity_instance.field_instances, identifier)
+}
+
+pub fn int_field(entity_instance: &EntityInstance, identifier: &str) -> Option<i32> {
+    match field(entity_instance, identifier)? {
+        FieldValue::Int(value) => *value,
+        _ => None,
+    }
+}
+
+pub fn float_field(entity_instance: &EntityInstance, identifier: &str) -> Option<f32> {
+    match field(entity_instance, identifier)? {
+        FieldValue::Float(value) => *value,
+        _ => None,
+    }
+}
+
+/// Reads an `Int` or a `Float` field, so designers can use either for numbers.
+pub fn number_field(entity_instance: &EntityInstance, identifier: &str) -> Option<f32> {
+    float_field(entity_instance, identifier)
+        .or_else(|| int_field(entity_instance, identifier).map(|value| value as f32))
+}
+
+/// Reads a `String` or an `Enum` field.
+pub fn string_field(entity_instance: &EntityInstance, identifier: &str) -> Option<String> {
+    match field(entity_instance, identifier)? {
+        FieldValue::String(value) | FieldValue::Enum(value) => value.clone(),
+        _ => None,
+    }
+}
+
+pub fn int_array_field(entity_instance: &EntityInstance, identifier: &str) -> Vec<usize> {
+    match field(entity_instance, identifier) {
+        Some(FieldValue::Ints(values)) => values
+            .iter()
+            .flatten()
+            .filter_map(|value| usize::try_from(*value).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reads an `Int` or a `Float` level field.
+pub fn level_number_field(level: &Level, identifier: &str) -> Option<f32> {
+    match find_field(&level.field_instances, identifier)? {
+        FieldValue::Float(value) => *value,
+        FieldValue::Int(value) => value.map(|value| value as f32),
+        _ => None,
+    }
+}
+
+/// Reads an `Array<String>` or `Array<FilePath>` level field. Empty entries stay as `None`
+/// so the array lines up with the level's other arrays.
+pub fn level_strings_field(level: &Level, identifier: &str) -> Vec<Option<String>> {
+    match find_field(&level.field_instances, identifier) {
+        Some(FieldValue::Strings(values) | FieldValue::FilePaths(values)) => values.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// Reads an `Array<Float>` or `Array<Int>` level field, keeping empty entries as `None`.
+pub fn level_numbers_field(level: &Level, identifier: &str) -> Vec<Option<f32>> {
+    match find_field(&level.field_instances, identifier) {
+        Some(FieldValue::Floats(values)) => values.clone(),
+        Some(FieldValue::Ints(values)) => values
+            .iter()
+            .map(|value| value.map(|value| value as f32))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn record_destroyed_terrain(
+    mut ev_tile_destroyed: EventReader<TileDestroyedEvent>,
+    level_selection: Res<LevelSelection>,
+    ldtk_worlds: Query<&Handle<LdtkAsset>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    mut destroyed_terrain: ResMut<DestroyedTerrain>,
+) {
+    if ev_tile_destroyed.is_empty() {
+        return;
+    }
+    let level = match current_level_identifier(&level_selection, &ldtk_worlds, &ldtk_assets) {
+        Some(level) => level,
+        None => {
+            ev_tile_destroyed.clear();
+            return;
+        }
+    };
+    let cells = destroyed_terrain.levels.entry(level).or_default();
+    cells.extend(
+        ev_tile_destroyed
+            .iter()
+            .filter_map(|tile_destroyed| tile_destroyed.grid_coords),
+    );
+}
+
+/// Despawns terrain destroyed on earlier visits once a level has finished spawning.
+fn restore_destroyed_terrain(
+    mut ev_level: EventReader<LevelEvent>,
+    destroyed_terrain: Res<DestroyedTerrain>,
+    levels: Query<(Entity, &Handle<LdtkLevel>)>,
+    ldtk_levels: Res<Assets<LdtkLevel>>,
+    layers: Query<&Parent>,
+    terrain_tiles: Query<(Entity, &GridCoords, &Parent), With<Terrain>>,
+    mut commands: Commands,
+) {
+    for level_event in ev_level.iter() {
+        let level_iid = match level_event {
+            LevelEvent::Transformed(iid) => iid,
+            _ => continue,
+        };
+        let level = levels.iter().find_map(|(entity, handle)| {
+            ldtk_levels
+                .get(handle)
+                .filter(|ldtk_level| &ldtk_level.level.iid == level_iid)
+                .map(|ldtk_level| (entity, &ldtk_level.level.identifier))
+        });
+        let (level_entity, identifier) = match level {
+            Some(level) => level,
+            None => continue,
+        };
+        let cells = match destroyed_terrain.levels.get(identifier) {
+            Some(cells) => cells,
+            None => continue,
+        };
+        for (entity, grid_coords, layer) in &terrain_tiles {
+            let in_level = layers
+                .get(layer.get())
+                .map(|level| level.get() == level_entity)
+                .unwrap_or(false);
+            if in_level && cells.contains(grid_coords) {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
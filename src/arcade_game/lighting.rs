@@ -0,0 +1,37 @@
+use crate::arcade_game::SceneState;
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use iyes_loopless::prelude::*;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// Drawn over everything in the level, under the UI.
+const LIGHT_MAP_Z: f32 = 500.0;
+const FLASH_TIME: f32 = 0.4;
+/// An explosion lights up this many times its blast radius.
+const FLASH_REACH: f32 = 3.0;
+/// Radians per second of a flickering light's waver.
+const FLICKER_SPEED: f32 = 12.0;
+
+/// Darkens levels with an `AmbientLight` below 1 and lights them back up around torches,
+/// projectiles and explosions. Light is worked out on the CPU one tile at a time and
+/// stopped by terrain, then drawn as a darkness overlay.
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(systems::spawn_light_maps)
+            .add_system(systems::light_projectiles)
+            .add_system(systems::spawn_flashes)
+            .add_system(systems::fade_flashes)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                // sees where everything ended up this frame and which terrain was destroyed
+                systems::update_light_maps.after(TransformSystem::TransformPropagate),
+            )
+            .add_enter_system(SceneState::Unloading, systems::cleanup_flashes);
+    }
+}
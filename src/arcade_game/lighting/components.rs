@@ -0,0 +1,198 @@
+use crate::arcade_game::ldtk;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+/// Lights up the tiles around it in dark levels.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct LightSource {
+    /// Reach in pixels.
+    pub radius: f32,
+    /// Brightness added at the centre, where 1 is fully lit.
+    pub intensity: f32,
+    /// How far the brightness wavers, from 0 for a steady light to 1.
+    pub flicker: f32,
+}
+
+impl LightSource {
+    pub const DEFAULT_RADIUS: f32 = 48.0;
+    pub const DEFAULT_INTENSITY: f32 = 1.0;
+    pub const PROJECTILE: Self = Self {
+        radius: 24.0,
+        intensity: 0.6,
+        flicker: 0.0,
+    };
+}
+
+impl Default for LightSource {
+    fn default() -> Self {
+        Self {
+            radius: Self::DEFAULT_RADIUS,
+            intensity: Self::DEFAULT_INTENSITY,
+            flicker: 0.0,
+        }
+    }
+}
+
+impl From<EntityInstance> for LightSource {
+    fn from(entity_instance: EntityInstance) -> Self {
+        let number = |identifier: &str, default: f32| {
+            ldtk::number_field(&entity_instance, identifier).unwrap_or(default)
+        };
+        Self {
+            radius: number(ldtk::RADIUS_FIELD, Self::DEFAULT_RADIUS),
+            intensity: number(ldtk::INTENSITY_FIELD, Self::DEFAULT_INTENSITY),
+            flicker: number(ldtk::FLICKER_FIELD, 0.0).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// A light placed in LDtk, e.g. a torch.
+#[derive(Bundle, LdtkEntity)]
+pub struct LightBundle {
+    #[from_entity_instance]
+    light: LightSource,
+    name: Name,
+}
+
+impl Default for LightBundle {
+    fn default() -> Self {
+        Self {
+            light: LightSource::default(),
+            name: Name::from(Self::DEFAULT_NAME),
+        }
+    }
+}
+
+impl LightBundle {
+    pub const DEFAULT_NAME: &str = "Light";
+}
+
+/// The light an explosion leaves behind for a moment.
+#[derive(Component)]
+pub struct Flash {
+    pub timer: Timer,
+    pub intensity: f32,
+}
+
+/// The darkness overlay of a level, one pixel per tile.
+#[derive(Component)]
+pub struct LightMap {
+    /// The level the overlay covers; the overlay goes when the level does.
+    pub level: Entity,
+    pub ambient: f32,
+    pub grid: LightGrid,
+    pub image: Handle<Image>,
+    /// Darkness of every tile as last written to `image`, so frames where nothing changed
+    /// leave the image alone instead of uploading it again.
+    pub darkness: Vec<u8>,
+    /// Set until the grid has been filled from the level's terrain.
+    pub rebuild: bool,
+}
+
+/// A light in grid space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLight {
+    /// Position in tiles from the level's bottom left corner.
+    pub position: Vec2,
+    /// Reach in tiles.
+    pub radius: f32,
+    pub intensity: f32,
+}
+
+/// Which tiles of a level stop light.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightGrid {
+    pub size: UVec2,
+    blocked: Vec<bool>,
+}
+
+impl LightGrid {
+    pub fn new(size: UVec2) -> Self {
+        Self {
+            size,
+            blocked: vec![false; (size.x * size.y) as usize],
+        }
+    }
+
+    fn index(&self, cell: IVec2) -> Option<usize> {
+        let in_bounds = cell.x >= 0
+            && cell.y >= 0
+            && (cell.x as u32) < self.size.x
+            && (cell.y as u32) < self.size.y;
+        in_bounds.then(|| (cell.y as u32 * self.size.x + cell.x as u32) as usize)
+    }
+
+    pub fn clear(&mut self) {
+        self.blocked.fill(false);
+    }
+
+    pub fn set_blocked(&mut self, cell: IVec2) {
+        if let Some(index) = self.index(cell) {
+            self.blocked[index] = true;
+        }
+    }
+
+    pub fn is_blocked(&self, cell: IVec2) -> bool {
+        self.index(cell).map_or(false, |index| self.blocked[index])
+    }
+
+    /// Whether light gets from one tile to another. Only the tiles in between can stop it,
+    /// so the faces of walls are lit.
+    pub fn visible(&self, from: IVec2, to: IVec2) -> bool {
+        // Bresenham's line, stepping from `from` towards `to`
+        let delta = (to - from).abs();
+        let step = (to - from).signum();
+        let mut cell = from;
+        let mut error = delta.x - delta.y;
+        loop {
+            if cell == to {
+                return true;
+            }
+            if cell != from && self.is_blocked(cell) {
+                return false;
+            }
+            let doubled = 2 * error;
+            if doubled > -delta.y {
+                error -= delta.y;
+                cell.x += step.x;
+            }
+            if doubled < delta.x {
+                error += delta.x;
+                cell.y += step.y;
+            }
+        }
+    }
+
+    /// Brightness of every tile, row by row from the bottom, from `ambient` up to 1.
+    pub fn brightness(&self, ambient: f32, lights: &[GridLight]) -> Vec<f32> {
+        let mut brightness = vec![ambient; self.blocked.len()];
+        for light in lights {
+            let from = light.position.floor().as_ivec2();
+            let min = (light.position - light.radius)
+                .floor()
+                .as_ivec2()
+                .max(IVec2::ZERO);
+            let max = (light.position + light.radius)
+                .ceil()
+                .as_ivec2()
+                .min(self.size.as_ivec2() - IVec2::ONE);
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let cell = IVec2::new(x, y);
+                    let distance = (cell.as_vec2() + 0.5).distance(light.position);
+                    if distance >= light.radius || !self.visible(from, cell) {
+                        continue;
+                    }
+                    let falloff = 1.0 - distance / light.radius;
+                    if let Some(index) = self.index(cell) {
+                        brightness[index] += light.intensity * falloff * falloff;
+                    }
+                }
+            }
+        }
+        for value in &mut brightness {
+            *value = value.min(1.0);
+        }
+        brightness
+    }
+}
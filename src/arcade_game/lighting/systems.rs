@@ -0,0 +1,205 @@
+use crate::arcade_game::combat::prelude::{ExplosionEvent, Projectile};
+use crate::arcade_game::ldtk;
+use crate::arcade_game::map::{Terrain, TILE_SIZE};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use bevy::sprite::Anchor;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+
+/// Covers a level in darkness as soon as it has spawned, if its `AmbientLight` is below 1.
+pub fn spawn_light_maps(
+    mut ev_level: EventReader<LevelEvent>,
+    levels: Query<(Entity, &Handle<LdtkLevel>)>,
+    ldtk_levels: Res<Assets<LdtkLevel>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    for level_event in ev_level.iter() {
+        let level_iid = match level_event {
+            LevelEvent::Spawned(iid) => iid,
+            _ => continue,
+        };
+        let level = levels.iter().find_map(|(entity, handle)| {
+            ldtk_levels
+                .get(handle)
+                .filter(|ldtk_level| &ldtk_level.level.iid == level_iid)
+                .map(|ldtk_level| (entity, &ldtk_level.level))
+        });
+        let (level_entity, level) = match level {
+            Some(level) => level,
+            None => continue,
+        };
+        let ambient = match ldtk::level_number_field(level, ldtk::AMBIENT_LIGHT_FIELD) {
+            Some(ambient) if ambient < 1.0 => ambient.max(0.0),
+            _ => continue,
+        };
+        let level_size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+        let grid_size = (level_size / TILE_SIZE).ceil().as_uvec2();
+
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: grid_size.x,
+                height: grid_size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+        );
+        // blends the light between tiles instead of showing it in blocks
+        image.sampler_descriptor = ImageSampler::linear();
+        let image = images.add(image);
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(level_size),
+                    anchor: Anchor::BottomLeft,
+                    ..default()
+                },
+                texture: image.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, super::LIGHT_MAP_Z),
+                ..default()
+            },
+            LightMap {
+                level: level_entity,
+                ambient,
+                grid: LightGrid::new(grid_size),
+                image,
+                darkness: vec![u8::MAX; (grid_size.x * grid_size.y) as usize],
+                rebuild: true,
+            },
+            Name::from("Light Map"),
+        ));
+    }
+}
+
+pub fn light_projectiles(projectiles: Query<Entity, Added<Projectile>>, mut commands: Commands) {
+    for projectile in &projectiles {
+        commands.entity(projectile).insert(LightSource::PROJECTILE);
+    }
+}
+
+pub fn spawn_flashes(mut ev_explosion: EventReader<ExplosionEvent>, mut commands: Commands) {
+    for explosion in ev_explosion.iter() {
+        let light = LightSource {
+            radius: explosion.radius * super::FLASH_REACH,
+            ..default()
+        };
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(explosion.translation)),
+            light,
+            Flash {
+                timer: Timer::from_seconds(super::FLASH_TIME, TimerMode::Once),
+                intensity: light.intensity,
+            },
+            Name::from("Flash"),
+        ));
+    }
+}
+
+pub fn fade_flashes(
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut Flash, &mut LightSource)>,
+    mut commands: Commands,
+) {
+    for (entity, mut flash, mut light) in &mut flashes {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            light.intensity = flash.intensity * flash.timer.percent_left();
+        }
+    }
+}
+
+pub fn cleanup_flashes(flashes: Query<Entity, With<Flash>>, mut commands: Commands) {
+    for flash in &flashes {
+        commands.entity(flash).despawn_recursive();
+    }
+}
+
+/// Relights every light map from the lights around it, refilling its grid when terrain has
+/// been added or destroyed, and despawns maps whose level has been despawned.
+#[allow(clippy::too_many_arguments)]
+pub fn update_light_maps(
+    time: Res<Time>,
+    levels: Query<&GlobalTransform>,
+    layers: Query<&Parent>,
+    terrain_tiles: Query<(&GridCoords, &Parent), With<Terrain>>,
+    added_terrain: Query<(), Added<Terrain>>,
+    removed_terrain: RemovedComponents<Terrain>,
+    lights: Query<(&GlobalTransform, &LightSource)>,
+    mut light_maps: Query<(Entity, &mut LightMap, &mut Transform)>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let terrain_changed = !added_terrain.is_empty() || removed_terrain.iter().next().is_some();
+    for (entity, mut light_map, mut transform) in &mut light_maps {
+        let level_origin = match levels.get(light_map.level) {
+            Ok(level) => level.translation().truncate(),
+            Err(_) => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        transform.translation = level_origin.extend(super::LIGHT_MAP_Z);
+
+        if terrain_changed || light_map.rebuild {
+            let level_entity = light_map.level;
+            let grid = &mut light_map.grid;
+            grid.clear();
+            for (grid_coords, layer) in &terrain_tiles {
+                let in_level = layers
+                    .get(layer.get())
+                    .map(|level| level.get() == level_entity)
+                    .unwrap_or(false);
+                if in_level {
+                    grid.set_blocked(IVec2::new(grid_coords.x, grid_coords.y));
+                }
+            }
+            light_map.rebuild = false;
+        }
+
+        let grid_lights: Vec<GridLight> = lights
+            .iter()
+            .map(|(light_transform, light)| {
+                let position = light_transform.translation().truncate();
+                // each light wavers on its own beat
+                let phase = position.x + position.y;
+                let waver =
+                    0.5 + 0.5 * (time.elapsed_seconds() * super::FLICKER_SPEED + phase).sin();
+                GridLight {
+                    position: (position - level_origin) / TILE_SIZE,
+                    radius: light.radius / TILE_SIZE.x,
+                    intensity: light.intensity * (1.0 - light.flicker * waver),
+                }
+            })
+            .collect();
+        let darkness: Vec<u8> = light_map
+            .grid
+            .brightness(light_map.ambient, &grid_lights)
+            .iter()
+            .map(|value| ((1.0 - value) * 255.0) as u8)
+            .collect();
+        if darkness == light_map.darkness {
+            continue;
+        }
+
+        let image = match images.get_mut(&light_map.image) {
+            Some(image) => image,
+            None => continue,
+        };
+        let size = light_map.grid.size;
+        for (index, value) in darkness.iter().enumerate() {
+            let (x, y) = (index as u32 % size.x, index as u32 / size.x);
+            // image rows run from the top, grid rows from the bottom
+            let pixel = (((size.y - 1 - y) * size.x + x) * 4) as usize;
+            image.data[pixel + 3] = *value;
+        }
+        light_map.darkness = darkness;
+    }
+}
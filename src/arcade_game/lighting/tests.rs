@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use super::components::*;
+
+fn light(x: f32, y: f32) -> GridLight {
+    GridLight {
+        position: Vec2::new(x, y),
+        radius: 6.0,
+        intensity: 1.0,
+    }
+}
+
+#[test]
+fn terrain_casts_shadows() {
+    let mut grid = LightGrid::new(UVec2::new(10, 3));
+    // a wall two tiles right of the light
+    for y in 0..3 {
+        grid.set_blocked(IVec2::new(3, y));
+    }
+    let brightness = grid.brightness(0.1, &[light(1.5, 1.5)]);
+    let at = |x: usize, y: usize| brightness[y * 10 + x];
+
+    assert!(at(2, 1) > 0.5, "next to the light");
+    assert!(at(3, 1) > 0.1, "the face of the wall");
+    assert_eq!(at(4, 1), 0.1, "behind the wall");
+    assert_eq!(at(9, 1), 0.1, "out of reach");
+}
+
+#[test]
+fn lights_add_up_to_full_brightness() {
+    let grid = LightGrid::new(UVec2::new(4, 4));
+    let brightness = grid.brightness(0.5, &[light(1.5, 1.5), light(1.5, 1.5)]);
+    assert_eq!(brightness[4 + 1], 1.0);
+    assert!(brightness.iter().all(|value| (0.5..=1.0).contains(value)));
+}
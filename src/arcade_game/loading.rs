@@ -0,0 +1,24 @@
+use crate::arcade_game::SceneState;
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// Holds the scene in `SceneState::Loading` until every tracked asset has loaded and the
+/// level has spawned, or sends it to `SceneState::Failed` when an asset is missing.
+///
+/// Audio is deliberately not tracked: the game plays fine without it.
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameAssets>()
+            .init_resource::<LoadingProgress>()
+            .add_enter_system(SceneState::Loading, systems::reset_progress)
+            .add_system(systems::track_progress.run_in_state(SceneState::Loading));
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::asset::{Asset, HandleId};
+use bevy::prelude::*;
+
+/// Handles the scene can't be played without.
+///
+/// Systems that load gameplay assets register their handles here; keeping them also keeps
+/// the assets alive between scenes.
+#[derive(Resource, Default)]
+pub struct GameAssets {
+    handles: Vec<HandleUntyped>,
+}
+
+impl GameAssets {
+    pub fn track<T: Asset>(&mut self, handle: &Handle<T>) {
+        self.track_untyped(handle.clone_untyped());
+    }
+
+    pub fn track_untyped(&mut self, handle: HandleUntyped) {
+        if !self.handles.iter().any(|tracked| tracked.id == handle.id) {
+            self.handles.push(handle);
+        }
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = HandleId> + '_ {
+        self.handles.iter().map(|handle| handle.id)
+    }
+}
+
+/// How far the current scene has got with loading.
+#[derive(Resource, Default, Debug)]
+pub struct LoadingProgress {
+    pub loaded: usize,
+    pub total: usize,
+    pub level_spawned: bool,
+    /// Paths of the assets that failed to load.
+    pub missing: Vec<String>,
+}
+
+impl LoadingProgress {
+    /// Share of the work done, from 0 to 1. Spawning the level counts as one more asset.
+    pub fn fraction(&self) -> f32 {
+        let done = self.loaded + usize::from(self.level_spawned);
+        done as f32 / (self.total + 1) as f32
+    }
+}
@@ -0,0 +1,56 @@
+use crate::arcade_game::SceneState;
+use bevy::asset::{HandleId, LoadState};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use iyes_loopless::prelude::*;
+
+use super::components::*;
+
+fn asset_path(asset_server: &AssetServer, id: HandleId) -> String {
+    match asset_server.get_handle_path(id) {
+        Some(path) => path.path().display().to_string(),
+        None => format!("{:?}", id),
+    }
+}
+
+pub fn reset_progress(mut progress: ResMut<LoadingProgress>) {
+    *progress = LoadingProgress::default();
+}
+
+pub fn track_progress(
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    mut progress: ResMut<LoadingProgress>,
+    mut ev_level: EventReader<LevelEvent>,
+    mut commands: Commands,
+) {
+    if ev_level
+        .iter()
+        .any(|level_event| matches!(level_event, LevelEvent::Transformed(_)))
+    {
+        progress.level_spawned = true;
+    }
+    progress.total = game_assets.ids().count();
+    progress.loaded = game_assets
+        .ids()
+        .filter(|id| asset_server.get_load_state(*id) == LoadState::Loaded)
+        .count();
+
+    match asset_server.get_group_load_state(game_assets.ids()) {
+        LoadState::Failed => {
+            progress.missing = game_assets
+                .ids()
+                .filter(|id| asset_server.get_load_state(*id) == LoadState::Failed)
+                .map(|id| asset_path(&asset_server, id))
+                .collect();
+            for path in &progress.missing {
+                error!("missing asset: {}", path);
+            }
+            commands.insert_resource(NextState(SceneState::Failed));
+        }
+        LoadState::Loaded if progress.level_spawned => {
+            commands.insert_resource(NextState(SceneState::Playing));
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,45 @@
+use crate::arcade_game::harness::TestGame;
+use crate::arcade_game::SceneState;
+use bevy::prelude::*;
+use iyes_loopless::prelude::*;
+
+use super::components::*;
+
+fn scene_state(world: &World) -> SceneState {
+    world.resource::<CurrentState<SceneState>>().0
+}
+
+#[test]
+fn scene_plays_once_assets_have_loaded() {
+    let mut game = TestGame::new();
+    game.start();
+    let playing = game.run_until(TestGame::LOAD_FRAMES, |world| {
+        scene_state(world) == SceneState::Playing
+    });
+    assert!(playing, "scene never left loading");
+
+    let progress = game.world().resource::<LoadingProgress>();
+    assert!(progress.total > 0);
+    assert_eq!(progress.loaded, progress.total);
+    assert_eq!(progress.fraction(), 1.0);
+}
+
+#[test]
+fn missing_asset_fails_loading() {
+    let mut game = TestGame::new();
+    let handle = game
+        .world()
+        .resource::<AssetServer>()
+        .load_untyped("missing.png");
+    game.world()
+        .resource_mut::<GameAssets>()
+        .track_untyped(handle);
+    game.start();
+    let failed = game.run_until(TestGame::LOAD_FRAMES, |world| {
+        scene_state(world) == SceneState::Failed
+    });
+    assert!(failed, "missing asset did not fail loading");
+
+    let progress = game.world().resource::<LoadingProgress>();
+    assert_eq!(progress.missing, vec!["missing.png".to_string()]);
+}
@@ -0,0 +1,47 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::utils::{BoxedFuture, HashMap};
+use components::*;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// Loads a string table per language and keeps `Locale` filled from the selected one.
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<StringTable>()
+            .add_asset_loader(StringTableLoader)
+            .init_resource::<Language>()
+            .init_resource::<Locale>()
+            .init_resource::<StringTableHandles>()
+            .add_startup_system(systems::load_language)
+            .add_startup_system(systems::load_string_tables)
+            .add_system(systems::apply_string_tables);
+    }
+}
+
+const LANG_DIR: &str = "lang";
+
+struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let strings: HashMap<String, String> = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(StringTable(strings)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lang.ron"]
+    }
+}
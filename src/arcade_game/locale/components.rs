@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Language the UI is shown in, picked in the options menu and kept in the settings.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Names the string table, `lang/<code>.lang.ron`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// The language's name for itself, so it can be found without reading the current one.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|language| *language == self);
+        Self::ALL[index.map_or(0, |index| (index + 1) % Self::ALL.len())]
+    }
+}
+
+/// Key/value UI strings of one language, loaded from `lang/<code>.lang.ron`.
+#[derive(TypeUuid, Debug, Clone, Default)]
+#[uuid = "2f8b6d4e-1c3a-4e5f-9b7d-8a6c4e2f0d13"]
+pub struct StringTable(pub HashMap<String, String>);
+
+#[derive(Resource, Default)]
+pub struct StringTableHandles(pub(crate) HashMap<Language, Handle<StringTable>>);
+
+/// UI strings in the selected language.
+///
+/// Keys missing from its table fall back to English, then to the key itself so a missing
+/// string shows up on screen instead of an empty label.
+#[derive(Resource, Default)]
+pub struct Locale {
+    language: Language,
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Locale {
+    pub fn new(
+        language: Language,
+        strings: HashMap<String, String>,
+        fallback: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            language,
+            strings,
+            fallback,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// `get` with every `{name}` in the string replaced by its value in `args`.
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        args.iter().fold(self.get(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    /// Every character the UI may draw in this language.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.strings
+            .values()
+            .chain(self.fallback.values())
+            .flat_map(|text| text.chars())
+    }
+}
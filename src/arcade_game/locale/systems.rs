@@ -0,0 +1,56 @@
+use crate::arcade_game::loading::components::GameAssets;
+use crate::arcade_game::settings::Settings;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+use super::components::*;
+
+/// Starts in the language picked last time.
+pub fn load_language(settings: Res<Settings>, mut language: ResMut<Language>) {
+    *language = settings.language;
+}
+
+pub fn load_string_tables(
+    asset_server: Res<AssetServer>,
+    mut handles: ResMut<StringTableHandles>,
+    mut game_assets: ResMut<GameAssets>,
+) {
+    for language in Language::ALL {
+        let path = PathBuf::from(super::LANG_DIR).join(format!("{}.lang.ron", language.code()));
+        let handle = asset_server.load(path);
+        game_assets.track(&handle);
+        handles.0.insert(language, handle);
+    }
+}
+
+/// Rebuilds `Locale` when the language changes or a string table is loaded or edited.
+pub fn apply_string_tables(
+    mut ev_asset: EventReader<AssetEvent<StringTable>>,
+    language: Res<Language>,
+    handles: Res<StringTableHandles>,
+    tables: Res<Assets<StringTable>>,
+    mut locale: ResMut<Locale>,
+) {
+    let tables_changed = ev_asset
+        .iter()
+        .filter(|event| {
+            matches!(
+                event,
+                AssetEvent::Created { .. } | AssetEvent::Modified { .. }
+            )
+        })
+        .count()
+        > 0;
+    if !tables_changed && !language.is_changed() {
+        return;
+    }
+    let strings = |language| {
+        handles
+            .0
+            .get(&language)
+            .and_then(|handle| tables.get(handle))
+            .map(|table| table.0.clone())
+            .unwrap_or_default()
+    };
+    *locale = Locale::new(*language, strings(*language), strings(Language::English));
+}
@@ -0,0 +1,42 @@
+use bevy::utils::HashMap;
+
+use super::components::*;
+
+fn table(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn missing_strings_fall_back_to_english_then_key() {
+    let locale = Locale::new(
+        Language::German,
+        table(&[("menu.play", "Spielen")]),
+        table(&[("menu.play", "Play"), ("menu.quit", "Quit")]),
+    );
+    assert_eq!(locale.get("menu.play"), "Spielen");
+    assert_eq!(locale.get("menu.quit"), "Quit");
+    assert_eq!(locale.get("menu.missing"), "menu.missing");
+}
+
+#[test]
+fn format_replaces_named_arguments() {
+    let locale = Locale::new(
+        Language::English,
+        table(&[("menu.load.slot", "Slot {slot} - {level}")]),
+        HashMap::default(),
+    );
+    let text = locale.format(
+        "menu.load.slot",
+        &[("slot", 2.to_string()), ("level", "Level_1".to_string())],
+    );
+    assert_eq!(text, "Slot 2 - Level_1");
+}
+
+#[test]
+fn next_language_wraps_around() {
+    assert_eq!(Language::English.next(), Language::German);
+    assert_eq!(Language::German.next(), Language::English);
+}
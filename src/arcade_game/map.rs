@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
 use bevy_ecs_ldtk::prelude::*;
 
 use crate::arcade_game::physics::prelude::*;
@@ -17,6 +18,12 @@ impl Terrain {
 #[derive(Component, Default)]
 pub struct Background;
 
+/// Terrain cells blown up so far, keyed by LDtk level identifier.
+#[derive(Resource, Default, Clone)]
+pub struct DestroyedTerrain {
+    pub levels: HashMap<String, HashSet<GridCoords>>,
+}
+
 #[derive(Bundle, LdtkIntCell)]
 pub struct BackgroundBundle {
     is_bg: Background,
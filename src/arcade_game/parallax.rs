@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+/// Depth of the farthest layer; nearer layers stack in front of it, all behind the level.
+/// The camera sees down to just below 0.
+const FARTHEST_Z: f32 = -0.9;
+const LAYER_Z_STEP: f32 = 0.01;
+
+/// Background images that scroll slower than the level as the camera moves, listed per
+/// LDtk level in its `ParallaxImages` and `ParallaxFactors` fields.
+pub struct ParallaxPlugin;
+
+impl Plugin for ParallaxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(systems::spawn_layers).add_system_to_stage(
+            CoreStage::PostUpdate,
+            // after every mode has moved the camera
+            systems::scroll_layers.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+/// One background image of a level.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ParallaxLayer {
+    /// How far the layer moves with the level as the camera pans: 0 stays put on screen
+    /// like a distant sky, 1 moves with the level.
+    pub factor: f32,
+    /// The level the layer belongs to; the layer goes when the level does.
+    pub level: Entity,
+    pub level_size: Vec2,
+}
+
+impl ParallaxLayer {
+    /// Where the layer sits when the camera looks at `camera`. It lines up with the level
+    /// when the camera is at the level's centre.
+    pub fn position(&self, level_center: Vec2, camera: Vec2) -> Vec2 {
+        level_center + (camera - level_center) * (1.0 - self.factor)
+    }
+}
+
+/// A layer as a level lists it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerSpec {
+    /// Path relative to the LDtk project.
+    pub image: String,
+    pub factor: f32,
+}
+
+/// Pairs the images of a level, farthest first, with their scroll factors. Images without
+/// a factor are spread evenly from 0 for the farthest towards 1.
+pub fn layer_specs(images: &[Option<String>], factors: &[Option<f32>]) -> Vec<LayerSpec> {
+    images
+        .iter()
+        .enumerate()
+        .filter_map(|(index, image)| {
+            let image = image.clone()?;
+            let factor = factors
+                .get(index)
+                .copied()
+                .flatten()
+                .unwrap_or(index as f32 / images.len() as f32);
+            Some(LayerSpec { image, factor })
+        })
+        .collect()
+}
@@ -0,0 +1,92 @@
+use crate::arcade_game::ldtk::{self, MapPath};
+use crate::arcade_game::loading::components::GameAssets;
+use crate::arcade_game::MainCamera;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use std::path::Path;
+
+use super::components::*;
+
+/// Loads and spawns the layers a level lists as soon as it has spawned, so the loading
+/// screen waits for their images.
+pub fn spawn_layers(
+    mut ev_level: EventReader<LevelEvent>,
+    levels: Query<(Entity, &Handle<LdtkLevel>)>,
+    ldtk_levels: Res<Assets<LdtkLevel>>,
+    map_path: Res<MapPath>,
+    asset_server: Res<AssetServer>,
+    mut game_assets: ResMut<GameAssets>,
+    mut commands: Commands,
+) {
+    for level_event in ev_level.iter() {
+        let level_iid = match level_event {
+            LevelEvent::Spawned(iid) => iid,
+            _ => continue,
+        };
+        let level = levels.iter().find_map(|(entity, handle)| {
+            ldtk_levels
+                .get(handle)
+                .filter(|ldtk_level| &ldtk_level.level.iid == level_iid)
+                .map(|ldtk_level| (entity, &ldtk_level.level))
+        });
+        let (level_entity, level) = match level {
+            Some(level) => level,
+            None => continue,
+        };
+        let images = ldtk::level_strings_field(level, ldtk::PARALLAX_IMAGES_FIELD);
+        let factors = ldtk::level_numbers_field(level, ldtk::PARALLAX_FACTORS_FIELD);
+        let level_size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+        // LDtk stores paths relative to the project file
+        let map_dir = Path::new(&map_path.0)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+
+        for (depth, spec) in layer_specs(&images, &factors).into_iter().enumerate() {
+            let texture: Handle<Image> = asset_server.load(map_dir.join(&spec.image));
+            game_assets.track(&texture);
+            commands.spawn((
+                SpriteBundle {
+                    texture,
+                    transform: Transform::from_xyz(
+                        0.0,
+                        0.0,
+                        super::FARTHEST_Z + depth as f32 * super::LAYER_Z_STEP,
+                    ),
+                    ..default()
+                },
+                ParallaxLayer {
+                    factor: spec.factor,
+                    level: level_entity,
+                    level_size,
+                },
+                Name::from("Parallax Layer"),
+            ));
+        }
+    }
+}
+
+/// Places every layer for where the main camera looks, and despawns layers whose level has
+/// been despawned.
+pub fn scroll_layers(
+    camera: Query<&Transform, (With<MainCamera>, Without<ParallaxLayer>)>,
+    levels: Query<&GlobalTransform>,
+    mut layers: Query<(Entity, &ParallaxLayer, &mut Transform)>,
+    mut commands: Commands,
+) {
+    let camera = match camera.get_single() {
+        Ok(transform) => transform.translation.truncate(),
+        Err(_) => return,
+    };
+    for (entity, layer, mut transform) in &mut layers {
+        // levels grow up and to the right from their origin
+        let level_center = match levels.get(layer.level) {
+            Ok(level) => level.translation().truncate() + layer.level_size / 2.0,
+            Err(_) => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        let position = layer.position(level_center, camera);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use super::components::*;
+
+#[test]
+fn missing_factors_spread_from_the_farthest_layer() {
+    let images = [
+        Some("sky.png".to_string()),
+        None,
+        Some("hills.png".to_string()),
+        Some("trees.png".to_string()),
+    ];
+    let specs = layer_specs(&images, &[None, None, None, Some(0.9)]);
+    assert_eq!(
+        specs,
+        vec![
+            LayerSpec {
+                image: "sky.png".to_string(),
+                factor: 0.0,
+            },
+            LayerSpec {
+                image: "hills.png".to_string(),
+                factor: 0.5,
+            },
+            LayerSpec {
+                image: "trees.png".to_string(),
+                factor: 0.9,
+            },
+        ]
+    );
+}
+
+#[test]
+fn layers_move_by_their_factor() {
+    let layer = |factor| ParallaxLayer {
+        factor,
+        level: Entity::from_raw(0),
+        level_size: Vec2::splat(256.0),
+    };
+    let center = Vec2::splat(128.0);
+    let camera = center + Vec2::new(100.0, -40.0);
+
+    assert_eq!(layer(0.0).position(center, center), center);
+    assert_eq!(layer(0.0).position(center, camera), camera);
+    assert_eq!(layer(1.0).position(center, camera), center);
+    assert_eq!(
+        layer(0.5).position(center, camera),
+        center + Vec2::new(50.0, -20.0)
+    );
+}
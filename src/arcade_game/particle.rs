@@ -0,0 +1,34 @@
+use crate::arcade_game::GameState;
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+pub use systems::cleanup_particles;
+use systems::*;
+
+pub mod components;
+pub mod prelude;
+mod systems;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DebrisTextureHandle::default())
+            .add_startup_system(setup)
+            .add_system_set(
+                ConditionSet::new()
+                    .run_in_state(GameState::InGame)
+                    .with_system(emit_debris)
+                    .with_system(emit_explosion)
+                    .with_system(update_particles)
+                    .into(),
+            );
+    }
+}
+
+const ENV_SPRITE_SHEET_PATH: &str = "env_tiles.png";
+const ENV_SPRITE_SHEET_COLUMNS: u32 = 8;
+const SMOKE_SPRITE_INDEX: usize = 165;
+const SPARK_SPRITE_INDEX: usize = 167;
+const DEBRIS_FRAGMENTS_PER_AXIS: u32 = 2;
+const PARTICLE_Z: f32 = 10.0;
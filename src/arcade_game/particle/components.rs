@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default)]
+pub struct DebrisTextureHandle(pub(crate) Handle<Image>);
+
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub gravity: f32,
+    pub lifetime: Timer,
+    pub fade: bool,
+}
+
+#[derive(Bundle)]
+pub struct DebrisParticleBundle {
+    pub sprite: SpriteBundle,
+    pub particle: Particle,
+}
+
+#[derive(Bundle)]
+pub struct EffectParticleBundle {
+    pub sprite: SpriteSheetBundle,
+    pub particle: Particle,
+}
+
+impl Particle {
+    pub const DEBRIS_GRAVITY: f32 = 200.0;
+    pub const DEBRIS_SPEED: f32 = 60.0;
+    pub const DEBRIS_LIFETIME: f32 = 0.8;
+    pub const SMOKE_GRAVITY: f32 = -15.0;
+    pub const SMOKE_SPEED: f32 = 10.0;
+    pub const SMOKE_LIFETIME: f32 = 1.2;
+    pub const SPARK_GRAVITY: f32 = 120.0;
+    pub const SPARK_SPEED: f32 = 90.0;
+    pub const SPARK_LIFETIME: f32 = 0.35;
+
+    pub fn new(velocity: Vec2, gravity: f32, lifetime: f32) -> Self {
+        Self {
+            velocity,
+            gravity,
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+            fade: true,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::arcade_game::combat::{events::*, prelude::SpriteSheetHandle};
+use crate::arcade_game::loading::components::GameAssets;
+use crate::arcade_game::map::TILE_SIZE;
+use crate::arcade_game::rng::ParticleRng;
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::components::*;
+
+fn tile_origin(texture_index: u32) -> Vec2 {
+    let column = texture_index % super::ENV_SPRITE_SHEET_COLUMNS;
+    let row = texture_index / super::ENV_SPRITE_SHEET_COLUMNS;
+    Vec2::new(column as f32, row as f32) * TILE_SIZE
+}
+
+fn random_direction(rng: &mut impl Rng, min_angle: f32, max_angle: f32) -> Vec2 {
+    Vec2::from_angle(rng.gen_range(min_angle..max_angle))
+}
+
+pub fn setup(
+    asset_server: Res<AssetServer>,
+    mut debris_texture: ResMut<DebrisTextureHandle>,
+    mut game_assets: ResMut<GameAssets>,
+) {
+    *debris_texture = DebrisTextureHandle(asset_server.load(super::ENV_SPRITE_SHEET_PATH));
+    game_assets.track(&debris_texture.0);
+}
+
+pub fn emit_debris(
+    mut ev_tile_destroyed: EventReader<TileDestroyedEvent>,
+    debris_texture: Res<DebrisTextureHandle>,
+    mut particle_rng: ResMut<ParticleRng>,
+    mut commands: Commands,
+) {
+    let rng = particle_rng.rng();
+    let fragment_size = TILE_SIZE / super::DEBRIS_FRAGMENTS_PER_AXIS as f32;
+    for tile in ev_tile_destroyed.iter() {
+        let origin = match tile.texture_index {
+            Some(texture_index) => tile_origin(texture_index),
+            None => continue,
+        };
+        for row in 0..super::DEBRIS_FRAGMENTS_PER_AXIS {
+            for column in 0..super::DEBRIS_FRAGMENTS_PER_AXIS {
+                let cell = Vec2::new(column as f32, row as f32);
+                let min = origin + cell * fragment_size;
+                // image rows grow downwards while world y grows upwards
+                let offset =
+                    ((cell + 0.5) * fragment_size - TILE_SIZE / 2.0) * Vec2::new(1.0, -1.0);
+                let velocity = Particle::DEBRIS_SPEED
+                    * rng.gen_range(0.5..1.0)
+                    * random_direction(rng, 0.2 * std::f32::consts::PI, 0.8 * std::f32::consts::PI);
+                commands.spawn(DebrisParticleBundle {
+                    sprite: SpriteBundle {
+                        sprite: Sprite {
+                            rect: Some(Rect {
+                                min,
+                                max: min + fragment_size,
+                            }),
+                            ..default()
+                        },
+                        texture: debris_texture.0.clone(),
+                        transform: Transform::from_translation(
+                            (tile.translation.truncate() + offset).extend(super::PARTICLE_Z),
+                        ),
+                        ..default()
+                    },
+                    particle: Particle::new(
+                        velocity,
+                        Particle::DEBRIS_GRAVITY,
+                        Particle::DEBRIS_LIFETIME,
+                    ),
+                });
+            }
+        }
+    }
+}
+
+pub fn emit_explosion(
+    mut ev_explosion: EventReader<ExplosionEvent>,
+    sprite_sheet: Res<SpriteSheetHandle>,
+    mut particle_rng: ResMut<ParticleRng>,
+    mut commands: Commands,
+) {
+    let rng = particle_rng.rng();
+    for explosion in ev_explosion.iter() {
+        let origin = explosion.translation.truncate();
+        let count = explosion.radius.ceil() as usize / 2 + 2;
+        let effects = [
+            (
+                super::SMOKE_SPRITE_INDEX,
+                Particle::SMOKE_SPEED,
+                Particle::SMOKE_GRAVITY,
+                Particle::SMOKE_LIFETIME,
+                0.5,
+            ),
+            (
+                super::SPARK_SPRITE_INDEX,
+                Particle::SPARK_SPEED,
+                Particle::SPARK_GRAVITY,
+                Particle::SPARK_LIFETIME,
+                0.25,
+            ),
+        ];
+        for (index, speed, gravity, lifetime, scale) in effects {
+            for _ in 0..count {
+                let velocity = speed
+                    * rng.gen_range(0.5..1.0)
+                    * random_direction(rng, 0.0, std::f32::consts::TAU);
+                let jitter =
+                    explosion.radius * 0.5 * random_direction(rng, 0.0, std::f32::consts::TAU);
+                commands.spawn(EffectParticleBundle {
+                    sprite: SpriteSheetBundle {
+                        sprite: TextureAtlasSprite::new(index),
+                        texture_atlas: sprite_sheet.0.clone(),
+                        transform: Transform::from_translation(
+                            (origin + jitter).extend(super::PARTICLE_Z),
+                        )
+                        .with_scale(scale * Vec3::ONE),
+                        ..default()
+                    },
+                    particle: Particle::new(velocity, gravity, lifetime),
+                });
+            }
+        }
+    }
+}
+
+pub fn update_particles(
+    time: Res<Time>,
+    mut particles: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        Option<&mut Sprite>,
+        Option<&mut TextureAtlasSprite>,
+    )>,
+    mut commands: Commands,
+) {
+    for (entity, mut particle, mut transform, sprite, atlas_sprite) in &mut particles {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let gravity = particle.gravity;
+        particle.velocity.y -= gravity * time.delta_seconds();
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+
+        if particle.fade {
+            let alpha = particle.lifetime.percent_left();
+            if let Some(mut sprite) = sprite {
+                sprite.color.set_a(alpha);
+            }
+            if let Some(mut atlas_sprite) = atlas_sprite {
+                atlas_sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
+
+pub fn cleanup_particles(particles: Query<Entity, With<Particle>>, mut commands: Commands) {
+    for particle in &particles {
+        commands.entity(particle).despawn_recursive();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::arcade_game::{FixedStep, FIXED_TIMESTEP, FIXED_UPDATE};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use events::*;
@@ -8,17 +9,57 @@ pub mod events;
 pub mod prelude;
 mod systems;
 
-pub struct PhysicsPlugin;
+pub struct PhysicsPlugin {
+    pub debug_render: bool,
+}
 
 impl Plugin for PhysicsPlugin {
-    #[allow(unused_variables)]
     fn build(&self, app: &mut App) {
-        app.add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
-            .add_plugin(RapierDebugRenderPlugin {
+        // Rapier is stepped from inside the fixed timestep so physics advances exactly
+        // once per gameplay tick, whatever the frame rate.
+        app.add_plugin(
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0)
+                .with_default_system_setup(false),
+        )
+        .add_fixed_timestep_system_set(
+            FIXED_UPDATE,
+            FixedStep::PhysicsSync.substage(),
+            RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsStages::SyncBackend),
+        )
+        .add_fixed_timestep_system_set(
+            FIXED_UPDATE,
+            FixedStep::PhysicsSyncFlush.substage(),
+            RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsStages::SyncBackendFlush),
+        )
+        .add_fixed_timestep_system_set(
+            FIXED_UPDATE,
+            FixedStep::PhysicsStep.substage(),
+            RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsStages::StepSimulation),
+        )
+        .add_fixed_timestep_system_set(
+            FIXED_UPDATE,
+            FixedStep::PhysicsWriteback.substage(),
+            RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsStages::Writeback),
+        )
+        .add_stage_before(
+            CoreStage::Last,
+            PhysicsStages::DetectDespawn,
+            SystemStage::parallel().with_system_set(
+                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsStages::DetectDespawn),
+            ),
+        );
+        let mut rapier_config = app.world.resource_mut::<RapierConfiguration>();
+        rapier_config.timestep_mode = TimestepMode::Fixed {
+            dt: FIXED_TIMESTEP,
+            substeps: 1,
+        };
+        if self.debug_render {
+            app.add_plugin(RapierDebugRenderPlugin {
                 enabled: false,
                 ..default()
-            })
-            .add_event::<components::FreezePhysicsEvent>()
+            });
+        }
+        app.add_event::<components::FreezePhysicsEvent>()
             .add_event::<components::UnfreezePhysicsEvent>()
             .add_system(systems::freeze_physics.run_if(systems::freeze_requested))
             .add_system(systems::unfreeze_physics.run_if(systems::unfreeze_requested));
@@ -17,6 +17,8 @@ pub struct UnfreezePhysicsEvent;
 #[reflect(Component)]
 pub struct Moveable {
     pub speed: f32,
+    /// Whether `speed` follows the tuning file; off once LDtk sets `MoveSpeed`.
+    pub tuned: bool,
 }
 
 #[derive(Bundle, Default, LdtkIntCell)]
@@ -39,21 +41,56 @@ pub struct StaticColliderBundle {
 
 impl Moveable {
     pub fn new(speed: f32) -> Self {
-        Self { speed }
+        Self {
+            speed,
+            tuned: false,
+        }
+    }
+
+    pub fn tuned(speed: f32) -> Self {
+        Self { speed, tuned: true }
     }
 }
 
 impl StaticColliderBundle {
     pub fn collision_groups() -> CollisionGroups {
-        CollisionGroups::new(Group::GROUP_2, Group::GROUP_1 | Group::GROUP_3)
+        CollisionGroups::new(
+            Group::GROUP_2,
+            Group::GROUP_1 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5,
+        )
     }
 }
 
 impl DynamicColliderBundle {
-    pub fn player_collision_groups() -> CollisionGroups {
+    /// Each local player has a group of its own, so shots can tell them apart.
+    pub fn player_group(player: usize) -> Group {
+        match player {
+            0 => Group::GROUP_1,
+            _ => Group::GROUP_5,
+        }
+    }
+
+    pub fn all_player_groups() -> Group {
+        Group::GROUP_1 | Group::GROUP_5
+    }
+
+    pub fn enemy_group() -> Group {
+        Group::GROUP_4
+    }
+
+    /// Players stand on terrain and can be hit by projectiles that target them.
+    pub fn player_collision_groups(player: usize) -> CollisionGroups {
         CollisionGroups {
-            memberships: Group::GROUP_1,
-            filters: Group::GROUP_2,
+            memberships: Self::player_group(player),
+            filters: Group::GROUP_2 | Group::GROUP_3,
+        }
+    }
+
+    /// Enemies stand on terrain and can be hit by projectiles that target them.
+    pub fn enemy_collision_groups() -> CollisionGroups {
+        CollisionGroups {
+            memberships: Self::enemy_group(),
+            filters: Group::GROUP_2 | Group::GROUP_3,
         }
     }
 
@@ -63,6 +100,14 @@ impl DynamicColliderBundle {
             filters: Group::GROUP_2 | Group::GROUP_3,
         }
     }
+
+    pub fn proj_collision_groups_hitting(targets: Group) -> CollisionGroups {
+        let groups = Self::proj_collision_groups();
+        CollisionGroups {
+            filters: groups.filters | targets,
+            ..groups
+        }
+    }
 }
 
 impl From<IntGridCell> for StaticColliderBundle {
@@ -107,21 +152,29 @@ impl From<IntGridCell> for StaticColliderBundle {
 
 impl From<EntityInstance> for DynamicColliderBundle {
     fn from(entity_instance: EntityInstance) -> Self {
-        let player_half_extents = PlayerBundle::DEFAULT_SCALE * character::CHARACTER_SIZE;
-        match entity_instance.identifier.as_ref() {
-            ldtk::PLAYER_ID => Self {
-                collider: Collider::cuboid(player_half_extents.x, player_half_extents.y),
-                rigid_body: RigidBody::Dynamic,
-                locked_axes: LockedAxes::ROTATION_LOCKED,
-                friction: Friction {
-                    coefficient: 0.0,
-                    ..default()
-                },
-                gravity_scale: GravityScale(GRAVITY_SCALE),
-                collision_groups: DynamicColliderBundle::player_collision_groups(),
+        let default_half_extents = PlayerBundle::DEFAULT_SCALE * character::CHARACTER_SIZE;
+        let half_extents = Vec2::new(
+            ldtk::number_field(&entity_instance, ldtk::COLLIDER_WIDTH_FIELD)
+                .map(|width| width / 2.0)
+                .unwrap_or(default_half_extents.x),
+            ldtk::number_field(&entity_instance, ldtk::COLLIDER_HEIGHT_FIELD)
+                .map(|height| height / 2.0)
+                .unwrap_or(default_half_extents.y),
+        );
+        let collision_groups = match entity_instance.identifier.as_ref() {
+            ldtk::PLAYER_ID => DynamicColliderBundle::player_collision_groups(0),
+            _ => DynamicColliderBundle::enemy_collision_groups(),
+        };
+        Self {
+            collider: Collider::cuboid(half_extents.x, half_extents.y),
+            rigid_body: RigidBody::Dynamic,
+            locked_axes: LockedAxes::ROTATION_LOCKED,
+            friction: Friction {
+                coefficient: 0.0,
                 ..default()
             },
-            _ => Self::default(),
+            gravity_scale: GravityScale(GRAVITY_SCALE),
+            collision_groups,
         }
     }
 }
@@ -0,0 +1,143 @@
+use crate::arcade_game::settings::Settings;
+use crate::arcade_game::versus::components::PlayerCamera;
+use crate::arcade_game::{MainCamera, VIEW_HEIGHT};
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_resource::{
+    Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::view::RenderLayers;
+use bevy::transform::TransformSystem;
+
+/// Size of the image the world is drawn into, in world pixels.
+pub const NATIVE_WIDTH: u32 = 320;
+pub const NATIVE_HEIGHT: u32 = VIEW_HEIGHT as u32;
+
+/// Only the display camera and the sprite showing the image live on this layer.
+const DISPLAY_LAYER: u8 = 1;
+/// Drawn after the world cameras, which render into the image.
+const DISPLAY_PRIORITY: isize = 2;
+const LETTERBOX_COLOR: Color = Color::BLACK;
+
+/// Draws the world into an image at the native resolution and shows it scaled up to the
+/// window, with black bars around it, so a world pixel is always a whole block of screen
+/// pixels. The UI is drawn straight to the window on top.
+pub struct PixelPerfectPlugin;
+
+impl Plugin for PixelPerfectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system_to_stage(StartupStage::PostStartup, setup_render_target)
+            .add_system(fit_display)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                snap_to_pixels.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+#[derive(Component)]
+struct PixelDisplay;
+
+fn setup_render_target(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<&mut Camera, With<MainCamera>>,
+) {
+    let size = Extent3d {
+        width: NATIVE_WIDTH,
+        height: NATIVE_HEIGHT,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("pixel_target"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..default()
+    };
+    // fills the image with zeroed pixels
+    image.resize(size);
+    let image = images.add(image);
+
+    for mut camera in &mut cameras {
+        camera.target = RenderTarget::Image(image.clone());
+    }
+
+    let display_layer = RenderLayers::layer(DISPLAY_LAYER);
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..default()
+        },
+        display_layer,
+        PixelDisplay,
+        Name::from("Pixel Display"),
+    ));
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                priority: DISPLAY_PRIORITY,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+            },
+            ..default()
+        },
+        display_layer,
+        Name::from("Display Camera"),
+    ));
+}
+
+/// Scales the image up to the largest size that fits the window, in whole steps of
+/// physical pixels with integer scaling.
+fn fit_display(
+    windows: Option<Res<Windows>>,
+    settings: Res<Settings>,
+    mut display: Query<&mut Transform, With<PixelDisplay>>,
+) {
+    let window = match windows.as_ref().and_then(|windows| windows.get_primary()) {
+        Some(window) => window,
+        None => return,
+    };
+    let fit = (window.width() / NATIVE_WIDTH as f32).min(window.height() / NATIVE_HEIGHT as f32);
+    let zoom = if settings.window.integer_scaling {
+        let scale_factor = window.scale_factor() as f32;
+        (fit * scale_factor).floor().max(1.0) / scale_factor
+    } else {
+        fit
+    };
+    for mut transform in &mut display {
+        if transform.scale.x != zoom {
+            transform.scale = Vec3::new(zoom, zoom, 1.0);
+        }
+    }
+}
+
+/// Rounds where sprites and cameras are drawn to whole world pixels, so nothing sits
+/// between two pixels of the image. Physics keeps the exact positions in `Transform`.
+fn snap_to_pixels(
+    mut transforms: Query<
+        &mut GlobalTransform,
+        Or<(
+            With<Sprite>,
+            With<TextureAtlasSprite>,
+            With<MainCamera>,
+            With<PlayerCamera>,
+        )>,
+    >,
+) {
+    for mut transform in &mut transforms {
+        let translation = transform.translation_mut();
+        translation.x = translation.x.round();
+        translation.y = translation.y.round();
+    }
+}
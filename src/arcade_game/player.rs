@@ -1,6 +1,8 @@
-use crate::arcade_game::{GameState, GameSystem};
+use crate::arcade_game::{FixedStep, GameState, GameSystem, SceneState, FIXED_UPDATE};
+use bevy::input::InputSystem;
 use bevy::prelude::*;
-use iyes_loopless::prelude::ConditionSet;
+use components::{PlayerBindings, PlayerInputs};
+use iyes_loopless::prelude::*;
 
 pub mod components;
 pub mod prelude;
@@ -10,8 +12,20 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(systems::add_weapon_trajectory)
-            .add_system_set(
+        app.init_resource::<PlayerInputs>()
+            .init_resource::<PlayerBindings>()
+            .add_system(systems::add_weapon_trajectory)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                systems::collect_input
+                    .run_in_state(GameState::InGame)
+                    .run_in_state(SceneState::Playing)
+                    .label(GameSystem::Input)
+                    .after(InputSystem),
+            )
+            .add_fixed_timestep_system_set(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
                 ConditionSet::new()
                     .label(GameSystem::Input)
                     .label(GameSystem::Movement)
@@ -21,3 +35,6 @@ impl Plugin for PlayerPlugin {
             );
     }
 }
+
+/// Local players sharing the keyboard.
+pub const MAX_PLAYERS: usize = 2;
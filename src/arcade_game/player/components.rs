@@ -3,6 +3,9 @@ use crate::arcade_game::combat::prelude::*;
 use crate::arcade_game::physics::prelude::*;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::{Index, IndexMut};
 
 #[derive(Component)]
 pub struct Player {
@@ -13,6 +16,148 @@ pub struct Player {
 #[derive(Component, Default)]
 pub struct PlayerChild;
 
+/// Which local player controls a character, and so which bindings and input it reads.
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PlayerId(pub usize);
+
+impl PlayerId {
+    pub const ONE: PlayerId = PlayerId(0);
+    pub const TWO: PlayerId = PlayerId(1);
+}
+
+/// One player's keyboard state, sampled every frame and consumed by the fixed gameplay tick.
+///
+/// `fire` latches a press until a tick handles it, so short taps are never lost
+/// between ticks. Replays store one of these per tick.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub left: bool,
+    pub right: bool,
+    pub aim_up: bool,
+    pub aim_down: bool,
+    pub fire: bool,
+}
+
+/// Input of every local player, indexed by [`PlayerId`]. Replays only cover player one.
+#[derive(Resource, Default)]
+pub struct PlayerInputs(pub [PlayerInput; super::MAX_PLAYERS]);
+
+impl Index<PlayerId> for PlayerInputs {
+    type Output = PlayerInput;
+
+    fn index(&self, player_id: PlayerId) -> &Self::Output {
+        &self.0[player_id.0]
+    }
+}
+
+impl IndexMut<PlayerId> for PlayerInputs {
+    fn index_mut(&mut self, player_id: PlayerId) -> &mut Self::Output {
+        &mut self.0[player_id.0]
+    }
+}
+
+/// Keys for one player's actions; any of the listed keys triggers the action.
+#[derive(Clone)]
+pub struct Bindings {
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub aim_up: Vec<KeyCode>,
+    pub aim_down: Vec<KeyCode>,
+    pub fire: Vec<KeyCode>,
+}
+
+impl Bindings {
+    /// One player on the whole keyboard.
+    pub fn solo() -> Self {
+        Self {
+            left: vec![KeyCode::A, KeyCode::Left],
+            right: vec![KeyCode::D, KeyCode::Right],
+            aim_up: vec![KeyCode::W, KeyCode::Up],
+            aim_down: vec![KeyCode::S, KeyCode::Down],
+            fire: vec![KeyCode::Space],
+        }
+    }
+
+    /// Left half of a shared keyboard.
+    pub fn left_hand() -> Self {
+        Self {
+            left: vec![KeyCode::A],
+            right: vec![KeyCode::D],
+            aim_up: vec![KeyCode::W],
+            aim_down: vec![KeyCode::S],
+            fire: vec![KeyCode::Space],
+        }
+    }
+
+    /// Right half of a shared keyboard.
+    pub fn right_hand() -> Self {
+        Self {
+            left: vec![KeyCode::Left],
+            right: vec![KeyCode::Right],
+            aim_up: vec![KeyCode::Up],
+            aim_down: vec![KeyCode::Down],
+            fire: vec![KeyCode::Return, KeyCode::RShift],
+        }
+    }
+
+    /// Samples the keyboard, keeping a fire press that no tick has handled yet.
+    pub fn read(&self, kb_input: &Input<KeyCode>, fire_latched: bool) -> PlayerInput {
+        PlayerInput {
+            left: kb_input.any_pressed(self.left.iter().copied()),
+            right: kb_input.any_pressed(self.right.iter().copied()),
+            aim_up: kb_input.any_pressed(self.aim_up.iter().copied()),
+            aim_down: kb_input.any_pressed(self.aim_down.iter().copied()),
+            fire: fire_latched || kb_input.any_just_pressed(self.fire.iter().copied()),
+        }
+    }
+}
+
+/// Bindings of every local player, indexed by [`PlayerId`].
+#[derive(Resource)]
+pub struct PlayerBindings(pub Vec<Bindings>);
+
+impl Default for PlayerBindings {
+    fn default() -> Self {
+        Self(vec![Bindings::solo()])
+    }
+}
+
+/// Lets the player fly through terrain, keeping what it replaced so it can be restored.
+// only the dev console's noclip adds it
+#[cfg_attr(not(feature = "dev"), allow(dead_code))]
+#[derive(Component)]
+pub struct NoClip {
+    pub collision_groups: CollisionGroups,
+    pub gravity_scale: GravityScale,
+}
+
+/// Keeps a player from moving, aiming or firing, e.g. after its turn is spent.
+#[derive(Component)]
+pub struct InputLocked;
+
+#[derive(Component, Default, Clone)]
+pub struct Inventory {
+    pub items: Vec<String>,
+}
+
+impl Inventory {
+    /// The weapon picked up last, the default one when there is none.
+    pub fn weapon(&self) -> Weapon {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|item| Weapon::from_name(item))
+            .unwrap_or_default()
+    }
+
+    /// Adds the weapon, or moves it to the end if already held, so it is fired next.
+    #[cfg_attr(not(feature = "dev"), allow(dead_code))]
+    pub fn equip(&mut self, weapon: Weapon) {
+        self.items.retain(|item| item != weapon.name());
+        self.items.push(weapon.name().to_string());
+    }
+}
+
 #[derive(Bundle, LdtkEntity)]
 pub struct PlayerBundle {
     #[sprite_sheet_bundle]
@@ -22,9 +167,9 @@ pub struct PlayerBundle {
     #[bundle]
     character_bundle: CharacterBundle,
     player: Player,
+    player_id: PlayerId,
     name: Name,
-    hp: HitPoint,
-    dmg: Damage,
+    inventory: Inventory,
 }
 
 impl Default for Player {
@@ -43,19 +188,34 @@ impl Default for PlayerBundle {
                 transform: Self::DEFAULT_TRANSFORM,
                 ..default()
             },
-            hp: HitPoint(100),
-            dmg: Damage(10),
             name: Name::from(Self::DEFAULT_NAME),
             character_bundle: CharacterBundle {
-                moveable: Moveable::new(PlayerBundle::DEFAULT_MOVE_SPEED),
+                moveable: Moveable::tuned(PlayerBundle::DEFAULT_MOVE_SPEED),
+                hit_points: HitPoint(CharacterBundle::DEFAULT_HIT_POINTS),
+                damage: Damage(CharacterBundle::DEFAULT_DAMAGE),
                 ..default()
             },
-            ..default()
+            player: Player::default(),
+            player_id: PlayerId::default(),
+            inventory: Inventory::default(),
         }
     }
 }
 
 impl PlayerBundle {
+    pub fn new(
+        player_id: PlayerId,
+        sprite_bundle: SpriteSheetBundle,
+        character_bundle: CharacterBundle,
+    ) -> Self {
+        Self {
+            sprite_bundle,
+            character_bundle,
+            player_id,
+            ..default()
+        }
+    }
+
     pub const DEFAULT_NAME: &str = "Player";
     pub const DEFAULT_SCALE: f32 = 1.;
     pub const DEFAULT_MOVE_SPEED: f32 = 35.0;
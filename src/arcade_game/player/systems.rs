@@ -1,4 +1,5 @@
 use crate::arcade_game::physics::prelude::*;
+use crate::arcade_game::FIXED_TIMESTEP;
 use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_rapier2d::prelude::*;
@@ -37,35 +38,57 @@ pub fn add_weapon_trajectory(
     }
 }
 
-pub fn handle_input(
+pub fn collect_input(
     kb_input: Res<Input<KeyCode>>,
-    time: Res<Time>,
-    mut player: Query<(
-        &mut Moveable,
-        &mut Player,
-        &mut KinematicCharacterController,
-        &mut TextureAtlasSprite,
-    )>,
+    bindings: Res<PlayerBindings>,
+    mut player_inputs: ResMut<PlayerInputs>,
 ) {
-    if player.is_empty() {
-        return;
+    for (player_input, bindings) in player_inputs.0.iter_mut().zip(&bindings.0) {
+        *player_input = bindings.read(&kb_input, player_input.fire);
     }
-    let (moveable, mut player, mut controller, mut sprite) = player.single_mut();
+}
 
-    let mut direction = Vec2::ZERO;
-    if kb_input.pressed(KeyCode::A) || kb_input.pressed(KeyCode::Left) {
-        direction.x -= 1.0;
-    }
-    if kb_input.pressed(KeyCode::D) || kb_input.pressed(KeyCode::Right) {
-        direction.x += 1.0;
-    }
-    if direction != Vec2::ZERO {
-        sprite.flip_x = direction.x > 0.0;
-        player.forward = direction;
-        direction *= time.delta_seconds();
-        controller.translation = match controller.translation {
-            Some(translation) => Some(translation + moveable.speed * direction),
-            None => Some(moveable.speed * direction),
-        };
+pub fn handle_input(
+    player_inputs: Res<PlayerInputs>,
+    mut players: Query<
+        (
+            &PlayerId,
+            &Moveable,
+            &mut Player,
+            &mut KinematicCharacterController,
+            &mut TextureAtlasSprite,
+            Option<&NoClip>,
+        ),
+        Without<InputLocked>,
+    >,
+) {
+    for (player_id, moveable, mut player, mut controller, mut sprite, noclip) in &mut players {
+        let player_input = &player_inputs[*player_id];
+        let mut direction = Vec2::ZERO;
+        if player_input.left {
+            direction.x -= 1.0;
+        }
+        if player_input.right {
+            direction.x += 1.0;
+        }
+        if noclip.is_some() {
+            if player_input.aim_up {
+                direction.y += 1.0;
+            }
+            if player_input.aim_down {
+                direction.y -= 1.0;
+            }
+        }
+        if direction != Vec2::ZERO {
+            if direction.x != 0.0 {
+                sprite.flip_x = direction.x > 0.0;
+                player.forward = Vec2::new(direction.x, 0.0);
+            }
+            direction *= FIXED_TIMESTEP;
+            controller.translation = match controller.translation {
+                Some(translation) => Some(translation + moveable.speed * direction),
+                None => Some(moveable.speed * direction),
+            };
+        }
     }
 }
@@ -0,0 +1,77 @@
+use crate::arcade_game::{FixedStep, GameState, GameSystem, SceneState, FIXED_UPDATE};
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub mod components;
+mod systems;
+#[cfg(test)]
+mod tests;
+
+pub use systems::{control_playback, save_recording, start_recording, stop_playback};
+
+/// Records every session to `replays/last.ron` and, given `file`, plays a replay back
+/// instead of reading the keyboard.
+pub struct ReplayPlugin {
+    pub file: Option<PathBuf>,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlaybackControls>()
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
+                systems::record_input
+                    .run_in_state(GameState::InGame)
+                    .run_in_state(SceneState::Playing)
+                    .run_if_resource_exists::<ReplayRecorder>()
+                    .before(GameSystem::Input),
+            )
+            .add_fixed_timestep_system(
+                FIXED_UPDATE,
+                FixedStep::Gameplay.substage(),
+                systems::play_input
+                    .run_in_state(GameState::InGame)
+                    .run_in_state(SceneState::Playing)
+                    .run_if_resource_exists::<ReplayPlayback>()
+                    .before(GameSystem::Input),
+            );
+        if let Some(file) = &self.file {
+            app.insert_resource(ReplayFile(file.clone()))
+                .add_startup_system(systems::play_replay_file);
+        }
+    }
+}
+
+const REPLAY_DIR: &str = "replays";
+const LAST_REPLAY: &str = "last.ron";
+const REPLAY_VERSION: u32 = 2;
+const MIN_PLAYBACK_SPEED: f32 = 0.125;
+const MAX_PLAYBACK_SPEED: f32 = 8.0;
+
+fn last_replay_path() -> PathBuf {
+    PathBuf::from(REPLAY_DIR).join(LAST_REPLAY)
+}
+
+pub fn read_replay(path: &Path) -> Result<Replay, ReplayError> {
+    let contents = fs::read_to_string(path)?;
+    let header: ReplayHeader = ron::from_str(&contents)?;
+    if header.version != REPLAY_VERSION {
+        return Err(ReplayError::UnsupportedVersion(header.version));
+    }
+    Ok(ron::from_str(&contents)?)
+}
+
+pub fn write_replay(path: &Path, replay: &Replay) -> Result<(), ReplayError> {
+    let contents = ron::ser::to_string_pretty(replay, ron::ser::PrettyConfig::default())?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
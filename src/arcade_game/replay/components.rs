@@ -0,0 +1,160 @@
+use crate::arcade_game::map::DestroyedTerrain;
+use crate::arcade_game::player::components::PlayerInput;
+use crate::arcade_game::save::components::{DestroyedCells, PendingLoad, SaveData};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, path::PathBuf};
+
+/// Everything needed to re-run a session tick for tick: the gameplay seed, the
+/// level it was played on, what the session started from and the input of every fixed
+/// tick since the player spawned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub level: String,
+    /// Terrain already destroyed when the session started, e.g. by an earlier session or
+    /// a continued save.
+    pub destroyed_terrain: Vec<DestroyedCells>,
+    /// The save the session continued from, which places the player.
+    pub loaded_save: Option<SaveData>,
+    pub ticks: Vec<PlayerInput>,
+}
+
+impl Replay {
+    /// The terrain and save to set up before the scene loads, so playback starts where
+    /// the recording did.
+    pub fn start_state(&self) -> (DestroyedTerrain, PendingLoad) {
+        (
+            DestroyedCells::to_terrain(&self.destroyed_terrain),
+            PendingLoad(self.loaded_save.clone()),
+        )
+    }
+}
+
+/// Read ahead of the full payload so replays from other versions are rejected cleanly.
+#[derive(Deserialize)]
+pub(super) struct ReplayHeader {
+    pub version: u32,
+}
+
+/// Replay passed on the command line, played instead of showing the main menu.
+#[derive(Resource)]
+pub struct ReplayFile(pub PathBuf);
+
+/// Collects the input of the running session.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub(crate) seed: u64,
+    pub(crate) destroyed_terrain: Vec<DestroyedCells>,
+    pub(crate) loaded_save: Option<SaveData>,
+    pub(crate) ticks: Vec<PlayerInput>,
+}
+
+impl ReplayRecorder {
+    pub fn new(
+        seed: u64,
+        destroyed_terrain: &DestroyedTerrain,
+        pending_load: &PendingLoad,
+    ) -> Self {
+        Self {
+            seed,
+            destroyed_terrain: DestroyedCells::from_terrain(destroyed_terrain),
+            loaded_save: pending_load.0.clone(),
+            ticks: Vec::new(),
+        }
+    }
+
+    pub fn to_replay(&self, level: String) -> Replay {
+        Replay {
+            version: super::REPLAY_VERSION,
+            seed: self.seed,
+            level,
+            destroyed_terrain: self.destroyed_terrain.clone(),
+            loaded_save: self.loaded_save.clone(),
+            ticks: self.ticks.clone(),
+        }
+    }
+}
+
+/// Feeds a recorded session back into player one's input instead of the keyboard.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub(crate) replay: Replay,
+    pub(crate) tick: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, tick: 0 }
+    }
+
+    pub fn next_input(&mut self) -> Option<PlayerInput> {
+        let input = self.replay.ticks.get(self.tick).copied();
+        if input.is_some() {
+            self.tick += 1;
+        }
+        input
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.replay.ticks.len()
+    }
+}
+
+/// In-window playback state: paused, single stepping and speed multiplier.
+#[derive(Resource)]
+pub struct PlaybackControls {
+    pub(crate) paused: bool,
+    pub(crate) stepping: bool,
+    pub(crate) speed: f32,
+}
+
+impl Default for PlaybackControls {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            stepping: false,
+            speed: 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::de::SpannedError),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "replay file io failed: {}", err),
+            ReplayError::Serialize(err) => write!(f, "could not serialize replay: {}", err),
+            ReplayError::Deserialize(err) => write!(f, "could not parse replay: {}", err),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "unsupported replay version {}", version)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+impl From<ron::Error> for ReplayError {
+    fn from(err: ron::Error) -> Self {
+        ReplayError::Serialize(err)
+    }
+}
+
+impl From<ron::de::SpannedError> for ReplayError {
+    fn from(err: ron::de::SpannedError) -> Self {
+        ReplayError::Deserialize(err)
+    }
+}
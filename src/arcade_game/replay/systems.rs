@@ -0,0 +1,149 @@
+use crate::arcade_game::ldtk;
+use crate::arcade_game::map::DestroyedTerrain;
+use crate::arcade_game::player::prelude::*;
+use crate::arcade_game::rng::GameRng;
+use crate::arcade_game::save::components::PendingLoad;
+use crate::arcade_game::{play_mode, GameMode, FIXED_TIMESTEP, FIXED_UPDATE};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use iyes_loopless::prelude::*;
+use std::time::Duration;
+
+use super::components::*;
+
+pub fn play_replay_file(replay_file: Res<ReplayFile>, mut commands: Commands) {
+    match super::read_replay(&replay_file.0) {
+        Ok(replay) => {
+            info!(
+                "playing back {} ({} ticks)",
+                replay_file.0.display(),
+                replay.ticks.len()
+            );
+            let (destroyed_terrain, pending_load) = replay.start_state();
+            commands.insert_resource(LevelSelection::Identifier(replay.level.clone()));
+            commands.insert_resource(GameRng::from_seed(replay.seed));
+            commands.insert_resource(destroyed_terrain);
+            commands.insert_resource(pending_load);
+            commands.insert_resource(ReplayPlayback::new(replay));
+            play_mode(GameMode::Arcade, commands);
+        }
+        Err(err) => error!("failed to read {}: {}", replay_file.0.display(), err),
+    }
+}
+
+/// Replays hold player one's input only, so other modes aren't recorded.
+pub fn start_recording(
+    game_rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    destroyed_terrain: Res<DestroyedTerrain>,
+    pending_load: Res<PendingLoad>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut commands: Commands,
+) {
+    if playback.is_none() && *mode == GameMode::Arcade {
+        commands.insert_resource(ReplayRecorder::new(
+            game_rng.seed(),
+            &destroyed_terrain,
+            &pending_load,
+        ));
+    }
+}
+
+/// Ticks only count once the player exists, so level loading time never shifts a replay.
+pub fn record_input(
+    player_inputs: Res<PlayerInputs>,
+    player: Query<(), With<Player>>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    if !player.is_empty() {
+        recorder.ticks.push(player_inputs[PlayerId::ONE]);
+    }
+}
+
+pub fn play_input(
+    player: Query<(), With<Player>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut player_inputs: ResMut<PlayerInputs>,
+) {
+    player_inputs[PlayerId::ONE] = if player.is_empty() {
+        PlayerInput::default()
+    } else {
+        playback.next_input().unwrap_or_default()
+    };
+}
+
+pub fn save_recording(
+    recorder: Option<Res<ReplayRecorder>>,
+    level_selection: Res<LevelSelection>,
+    ldtk_worlds: Query<&Handle<LdtkAsset>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    mut commands: Commands,
+) {
+    let recorder = match recorder {
+        Some(recorder) => recorder,
+        None => return,
+    };
+    commands.remove_resource::<ReplayRecorder>();
+    let level = match ldtk::current_level_identifier(&level_selection, &ldtk_worlds, &ldtk_assets) {
+        Some(level) => level,
+        None => return,
+    };
+    let path = super::last_replay_path();
+    match super::write_replay(&path, &recorder.to_replay(level)) {
+        Ok(()) => info!("wrote replay to {}", path.display()),
+        Err(err) => error!("failed to write {}: {}", path.display(), err),
+    }
+}
+
+pub fn stop_playback(mut timesteps: ResMut<FixedTimesteps>, mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+    commands.insert_resource(PlaybackControls::default());
+    if let Some(timestep) = timesteps.get_mut(FIXED_UPDATE) {
+        timestep.step = Duration::from_secs_f32(FIXED_TIMESTEP);
+        timestep.unpause();
+    }
+}
+
+/// `P` pauses, `.` advances a single tick while paused, `[` and `]` halve and double the speed.
+pub fn control_playback(
+    kb_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    playback: Res<ReplayPlayback>,
+    mut controls: ResMut<PlaybackControls>,
+    mut timesteps: ResMut<FixedTimesteps>,
+) {
+    let timestep = match timesteps.get_mut(FIXED_UPDATE) {
+        Some(timestep) => timestep,
+        None => return,
+    };
+    // the tick requested last frame has run by now
+    controls.stepping = false;
+
+    if kb_input.just_pressed(KeyCode::P) {
+        controls.paused = !controls.paused;
+    }
+    if kb_input.just_pressed(KeyCode::RBracket) {
+        controls.speed = (controls.speed * 2.0).min(super::MAX_PLAYBACK_SPEED);
+        info!("playback speed x{}", controls.speed);
+    }
+    if kb_input.just_pressed(KeyCode::LBracket) {
+        controls.speed = (controls.speed / 2.0).max(super::MIN_PLAYBACK_SPEED);
+        info!("playback speed x{}", controls.speed);
+    }
+    if playback.is_finished() && !controls.paused {
+        info!("replay finished after {} ticks", playback.tick);
+        controls.paused = true;
+    }
+    timestep.step = Duration::from_secs_f32(FIXED_TIMESTEP / controls.speed);
+
+    if controls.paused && !playback.is_finished() && kb_input.just_pressed(KeyCode::Period) {
+        controls.stepping = true;
+        // adding this frame's delta tops the accumulator up to exactly one step
+        timestep.accumulator = timestep.step.saturating_sub(time.delta());
+    }
+    if controls.paused && !controls.stepping {
+        timestep.pause();
+    } else {
+        timestep.unpause();
+    }
+}
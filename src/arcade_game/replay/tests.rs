@@ -0,0 +1,77 @@
+use crate::arcade_game::harness::{InputAction, TestGame};
+use crate::arcade_game::map::DestroyedTerrain;
+use crate::arcade_game::player::prelude::Player;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+
+const FLIGHT_FRAMES: usize = 300;
+const PLAYBACK_FRAMES: usize = 5000;
+
+fn destroyed_level(world: &World) -> Option<(String, Vec<GridCoords>)> {
+    let (level, cells) = world.resource::<DestroyedTerrain>().levels.iter().next()?;
+    let mut cells: Vec<GridCoords> = cells.iter().copied().collect();
+    cells.sort_by_key(|cell| (cell.x, cell.y));
+    Some((level.clone(), cells))
+}
+
+fn player_position(world: &mut World) -> Vec3 {
+    world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(world)
+        .translation
+}
+
+#[test]
+fn playback_reproduces_recorded_session() {
+    let mut game = TestGame::new();
+    game.start().wait_for_level();
+    game.step(&[InputAction::Press(KeyCode::D)])
+        .run(30)
+        .step(&[InputAction::Release(KeyCode::D)])
+        .step(&[InputAction::Press(KeyCode::W)])
+        .run(20)
+        .step(&[InputAction::Release(KeyCode::W)])
+        .step(&[InputAction::Press(KeyCode::Space)])
+        .step(&[InputAction::Release(KeyCode::Space)]);
+    let hit = game.run_until(FLIGHT_FRAMES, |world| destroyed_level(world).is_some());
+    assert!(hit, "projectile never hit the terrain");
+    game.run(1);
+
+    let (level, recorded_cells) = destroyed_level(game.world()).unwrap();
+    let recorded_position = player_position(game.world());
+    let replay = game.world().resource::<ReplayRecorder>().to_replay(level);
+
+    let mut playback = TestGame::new();
+    playback.play(replay);
+    let finished = playback.run_until(PLAYBACK_FRAMES, |world| {
+        world.resource::<ReplayPlayback>().is_finished()
+    });
+    assert!(
+        finished,
+        "replay did not finish within {} frames",
+        PLAYBACK_FRAMES
+    );
+
+    assert_eq!(destroyed_level(playback.world()).unwrap().1, recorded_cells);
+    assert_eq!(player_position(playback.world()), recorded_position);
+}
+
+#[test]
+fn playback_starts_from_recorded_terrain() {
+    let mut destroyed = DestroyedTerrain::default();
+    destroyed
+        .levels
+        .entry("Level_0".to_string())
+        .or_default()
+        .insert(GridCoords::new(3, 4));
+    let recorder = ReplayRecorder::new(7, &destroyed, &Default::default());
+    let replay = recorder.to_replay("Level_0".to_string());
+
+    let mut playback = TestGame::new();
+    playback.play(replay).wait_for_level();
+    let (level, cells) = destroyed_level(playback.world()).unwrap();
+    assert_eq!(level, "Level_0");
+    assert!(cells.contains(&GridCoords::new(3, 4)));
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The only source of randomness for gameplay, so a run can be reproduced from its seed.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.rng
+    }
+}
+
+/// Randomness for visual effects. Seeded like `GameRng` so replays look the same, but on
+/// its own stream, so how many particles are drawn never changes gameplay.
+#[derive(Resource)]
+pub struct ParticleRng(ChaCha8Rng);
+
+impl Default for ParticleRng {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+impl ParticleRng {
+    const STREAM: u64 = 1;
+
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(Self::STREAM);
+        Self(rng)
+    }
+
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.0
+    }
+}
+
+/// Restarts the sequences from the seed whenever a scene is set up.
+pub fn reset_rng(mut game_rng: ResMut<GameRng>, mut particle_rng: ResMut<ParticleRng>) {
+    let seed = game_rng.seed();
+    info!("gameplay rng seed: {}", seed);
+    *game_rng = GameRng::from_seed(seed);
+    *particle_rng = ParticleRng::from_seed(seed);
+}
@@ -0,0 +1,89 @@
+use crate::arcade_game::GameState;
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+use std::{fs, path::PathBuf, time::SystemTime};
+
+pub mod components;
+mod systems;
+
+pub use systems::autosave;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveSaveSlot>()
+            .init_resource::<PendingLoad>()
+            .add_system(systems::apply_pending_load.run_in_state(GameState::InGame));
+    }
+}
+
+pub const SAVE_SLOTS: usize = 3;
+const SAVE_DIR: &str = "saves";
+const SAVE_VERSION: u32 = 1;
+
+fn slot_path(slot: usize) -> PathBuf {
+    PathBuf::from(SAVE_DIR).join(format!("slot_{}.ron", slot + 1))
+}
+
+pub fn read_slot(slot: usize) -> Result<SaveData, SaveError> {
+    let contents = fs::read_to_string(slot_path(slot))?;
+    let header: SaveHeader = ron::from_str(&contents)?;
+    if header.version != SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(header.version));
+    }
+    Ok(ron::from_str(&contents)?)
+}
+
+pub fn write_slot(slot: usize, data: &SaveData) -> Result<(), SaveError> {
+    let contents = ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())?;
+    fs::create_dir_all(SAVE_DIR)?;
+    fs::write(slot_path(slot), contents)?;
+    Ok(())
+}
+
+/// Summaries of every slot, `None` for slots without a readable save.
+pub fn list_slots() -> Vec<Option<SaveSlotInfo>> {
+    (0..SAVE_SLOTS)
+        .map(|slot| {
+            let data = read_slot(slot).ok()?;
+            let modified = fs::metadata(slot_path(slot))
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            Some(SaveSlotInfo {
+                level: data.level,
+                modified,
+            })
+        })
+        .collect()
+}
+
+pub fn latest_slot() -> Option<usize> {
+    list_slots()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(slot, info)| info.map(|info| (slot, info.modified)))
+        .max_by_key(|(_, modified)| *modified)
+        .map(|(slot, _)| slot)
+}
+
+/// The first slot without a save, or the least recently saved one when all are taken.
+pub fn free_slot() -> usize {
+    let slots = list_slots();
+    if let Some(slot) = slots.iter().position(|info| info.is_none()) {
+        return slot;
+    }
+    let oldest = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, info)| info.as_ref().map(|info| (slot, info.modified)))
+        .min_by_key(|(_, modified)| *modified)
+        .map(|(slot, _)| slot)
+        .unwrap_or_default();
+    warn!(
+        "every save slot is taken, the new game will save over slot {}",
+        oldest + 1
+    );
+    oldest
+}
@@ -0,0 +1,113 @@
+use crate::arcade_game::map::DestroyedTerrain;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, time::SystemTime};
+
+#[derive(Resource, Default)]
+pub struct ActiveSaveSlot(pub usize);
+
+/// Save data waiting for the level to finish spawning before it is applied.
+#[derive(Resource, Default)]
+pub struct PendingLoad(pub Option<SaveData>);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    pub level: String,
+    pub player_position: [f32; 2],
+    pub hit_points: u8,
+    pub inventory: Vec<String>,
+    pub destroyed_terrain: Vec<DestroyedCells>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DestroyedCells {
+    pub level: String,
+    pub cells: Vec<[i32; 2]>,
+}
+
+/// Read ahead of the full payload so old or newer saves are rejected cleanly.
+#[derive(Deserialize)]
+pub(super) struct SaveHeader {
+    pub version: u32,
+}
+
+impl SaveData {
+    pub fn destroyed_terrain(&self) -> DestroyedTerrain {
+        DestroyedCells::to_terrain(&self.destroyed_terrain)
+    }
+}
+
+impl DestroyedCells {
+    pub fn from_terrain(destroyed_terrain: &DestroyedTerrain) -> Vec<DestroyedCells> {
+        destroyed_terrain
+            .levels
+            .iter()
+            .map(|(level, cells)| DestroyedCells {
+                level: level.clone(),
+                cells: cells.iter().map(|coords| [coords.x, coords.y]).collect(),
+            })
+            .collect()
+    }
+
+    pub fn to_terrain(destroyed: &[DestroyedCells]) -> DestroyedTerrain {
+        DestroyedTerrain {
+            levels: destroyed
+                .iter()
+                .map(|destroyed| {
+                    let cells = destroyed
+                        .cells
+                        .iter()
+                        .map(|[x, y]| GridCoords::new(*x, *y))
+                        .collect();
+                    (destroyed.level.clone(), cells)
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct SaveSlotInfo {
+    pub level: String,
+    pub modified: SystemTime,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::de::SpannedError),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "save file io failed: {}", err),
+            SaveError::Serialize(err) => write!(f, "could not serialize save: {}", err),
+            SaveError::Deserialize(err) => write!(f, "could not parse save: {}", err),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "unsupported save version {}", version)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(err: ron::Error) -> Self {
+        SaveError::Serialize(err)
+    }
+}
+
+impl From<ron::de::SpannedError> for SaveError {
+    fn from(err: ron::de::SpannedError) -> Self {
+        SaveError::Deserialize(err)
+    }
+}
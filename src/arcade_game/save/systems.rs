@@ -0,0 +1,61 @@
+use crate::arcade_game::combat::prelude::HitPoint;
+use crate::arcade_game::ldtk;
+use crate::arcade_game::map::DestroyedTerrain;
+use crate::arcade_game::player::prelude::*;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+
+pub fn autosave(
+    player: Query<(&Transform, &HitPoint, &Inventory), With<Player>>,
+    level_selection: Res<LevelSelection>,
+    ldtk_worlds: Query<&Handle<LdtkAsset>>,
+    ldtk_assets: Res<Assets<LdtkAsset>>,
+    destroyed_terrain: Res<DestroyedTerrain>,
+    active_slot: Res<ActiveSaveSlot>,
+) {
+    let level = match ldtk::current_level_identifier(&level_selection, &ldtk_worlds, &ldtk_assets) {
+        Some(level) => level,
+        None => return,
+    };
+    let (transform, hit_points, inventory) = match player.get_single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let data = SaveData {
+        version: super::SAVE_VERSION,
+        level,
+        player_position: transform.translation.truncate().to_array(),
+        hit_points: hit_points.0,
+        inventory: inventory.items.clone(),
+        destroyed_terrain: DestroyedCells::from_terrain(&destroyed_terrain),
+    };
+    match super::write_slot(active_slot.0, &data) {
+        Ok(()) => info!("saved progress to slot {}", active_slot.0 + 1),
+        Err(err) => error!("failed to save slot {}: {}", active_slot.0 + 1, err),
+    }
+}
+
+pub fn apply_pending_load(
+    mut ev_level: EventReader<LevelEvent>,
+    mut pending_load: ResMut<PendingLoad>,
+    mut player: Query<(&mut Transform, &mut HitPoint, &mut Inventory), With<Player>>,
+) {
+    let level_ready = ev_level
+        .iter()
+        .any(|level_event| matches!(level_event, LevelEvent::Transformed(_)));
+    if !level_ready {
+        return;
+    }
+    let data = match pending_load.0.take() {
+        Some(data) => data,
+        None => return,
+    };
+    if let Ok((mut transform, mut hit_points, mut inventory)) = player.get_single_mut() {
+        transform.translation.x = data.player_position[0];
+        transform.translation.y = data.player_position[1];
+        hit_points.0 = data.hit_points;
+        inventory.items = data.inventory;
+    }
+}
@@ -0,0 +1,241 @@
+use crate::arcade_game::locale::components::Language;
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, io, path::Path};
+
+#[cfg(test)]
+mod tests;
+
+const SETTINGS_PATH: &str = "settings.ron";
+
+/// Player settings read from `settings.ron` next to the game before the window opens.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window: WindowSettings,
+    pub audio: AudioSettings,
+    pub language: Language,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    /// Fullscreen at the desktop resolution, without changing the video mode.
+    Borderless,
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    /// Logical size of the window when windowed.
+    pub width: f32,
+    pub height: f32,
+    pub mode: DisplayMode,
+    pub vsync: bool,
+    /// Zooms by whole steps only, so every world pixel covers the same number of screen
+    /// pixels.
+    pub integer_scaling: bool,
+    /// Draws the world at its native resolution and scales the picture up to the window.
+    pub pixel_perfect: bool,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            width: 1280.0,
+            height: 720.0,
+            mode: DisplayMode::Windowed,
+            vsync: true,
+            integer_scaling: false,
+            pixel_perfect: true,
+        }
+    }
+}
+
+/// Volume channels, each in the `0.0..=1.0` range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.6,
+            sfx: 0.8,
+        }
+    }
+}
+
+/// Share of the full volume one press of a volume option adds.
+const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl AudioSettings {
+    pub fn volume(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Sfx => self.sfx,
+        }
+    }
+
+    /// Turns a channel up by a step, wrapping around to silent after full volume.
+    pub fn step_volume(&mut self, channel: VolumeChannel) {
+        let volume = match channel {
+            VolumeChannel::Master => &mut self.master,
+            VolumeChannel::Music => &mut self.music,
+            VolumeChannel::Sfx => &mut self.sfx,
+        };
+        *volume = if *volume >= 1.0 {
+            0.0
+        } else {
+            // rounded so repeated steps land on whole percentages
+            (((*volume + VOLUME_STEP) / VOLUME_STEP).round() * VOLUME_STEP).min(1.0)
+        };
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        (self.master * self.music).clamp(0.0, 1.0)
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        (self.master * self.sfx).clamp(0.0, 1.0)
+    }
+}
+
+impl WindowSettings {
+    pub fn descriptor(&self, title: &str) -> WindowDescriptor {
+        WindowDescriptor {
+            width: self.width,
+            height: self.height,
+            title: title.to_string(),
+            present_mode: if self.vsync {
+                PresentMode::AutoVsync
+            } else {
+                PresentMode::AutoNoVsync
+            },
+            mode: match self.mode {
+                DisplayMode::Windowed => WindowMode::Windowed,
+                DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+                DisplayMode::Fullscreen => WindowMode::Fullscreen,
+            },
+            ..default()
+        }
+    }
+
+    /// Overrides the file with `--width <px>`, `--height <px>`, `--windowed`, `--fullscreen`,
+    /// `--borderless`, `--vsync`, `--no-vsync`, `--integer-scaling` and `--no-pixel-perfect`.
+    pub fn apply_args(&mut self, args: &[String]) {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--width" => parse_value(arg, args.next(), &mut self.width),
+                "--height" => parse_value(arg, args.next(), &mut self.height),
+                "--windowed" => self.mode = DisplayMode::Windowed,
+                "--fullscreen" => self.mode = DisplayMode::Fullscreen,
+                "--borderless" => self.mode = DisplayMode::Borderless,
+                "--vsync" => self.vsync = true,
+                "--no-vsync" => self.vsync = false,
+                "--integer-scaling" => self.integer_scaling = true,
+                "--no-pixel-perfect" => self.pixel_perfect = false,
+                _ => {}
+            }
+        }
+    }
+}
+
+// runs before logging is set up, so problems go straight to stderr
+fn parse_value(flag: &str, value: Option<&String>, target: &mut f32) {
+    match value.map(|value| value.parse()) {
+        Some(Ok(parsed)) => *target = parsed,
+        _ => eprintln!("{} expects a number, ignoring it", flag),
+    }
+}
+
+impl Settings {
+    /// Reads the settings file, writing the defaults on the first run so they can be edited.
+    pub fn load() -> Self {
+        let path = Path::new(SETTINGS_PATH);
+        match read_settings(path) {
+            Ok(settings) => settings,
+            Err(SettingsError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+                let settings = Settings::default();
+                if let Err(err) = write_settings(path, &settings) {
+                    eprintln!("{}", err);
+                }
+                settings
+            }
+            Err(err) => {
+                eprintln!("{}, using the defaults", err);
+                Settings::default()
+            }
+        }
+    }
+
+    /// Writes the settings back to the file, e.g. after they were changed in the options.
+    pub fn save(&self) {
+        if let Err(err) = write_settings(Path::new(SETTINGS_PATH), self) {
+            error!("{}", err);
+        }
+    }
+}
+
+fn read_settings(path: &Path) -> Result<Settings, SettingsError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(ron::from_str(&contents)?)
+}
+
+fn write_settings(path: &Path, settings: &Settings) -> Result<(), SettingsError> {
+    let contents = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::de::SpannedError),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(err) => write!(f, "settings file io failed: {}", err),
+            SettingsError::Serialize(err) => write!(f, "could not serialize settings: {}", err),
+            SettingsError::Deserialize(err) => write!(f, "could not parse settings: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(err: io::Error) -> Self {
+        SettingsError::Io(err)
+    }
+}
+
+impl From<ron::Error> for SettingsError {
+    fn from(err: ron::Error) -> Self {
+        SettingsError::Serialize(err)
+    }
+}
+
+impl From<ron::de::SpannedError> for SettingsError {
+    fn from(err: ron::de::SpannedError) -> Self {
+        SettingsError::Deserialize(err)
+    }
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn args_override_window_settings() {
+    let mut window = WindowSettings::default();
+    window.apply_args(&args(&[
+        "--width",
+        "1920",
+        "--height",
+        "1080",
+        "--borderless",
+        "--no-vsync",
+        "--integer-scaling",
+        "--no-pixel-perfect",
+    ]));
+    assert_eq!(window.width, 1920.0);
+    assert_eq!(window.height, 1080.0);
+    assert_eq!(window.mode, DisplayMode::Borderless);
+    assert!(!window.vsync);
+    assert!(window.integer_scaling);
+    assert!(!window.pixel_perfect);
+}
+
+#[test]
+fn bad_size_keeps_the_file_value() {
+    let mut window = WindowSettings::default();
+    window.apply_args(&args(&["--width", "wide", "--height"]));
+    assert_eq!(window.width, WindowSettings::default().width);
+    assert_eq!(window.height, WindowSettings::default().height);
+}
+
+#[test]
+fn windowed_flag_overrides_fullscreen_file() {
+    let mut window = WindowSettings {
+        mode: DisplayMode::Fullscreen,
+        ..default()
+    };
+    window.apply_args(&args(&["--windowed"]));
+    assert_eq!(window.mode, DisplayMode::Windowed);
+}
+
+#[test]
+fn volume_steps_up_and_wraps_to_silent() {
+    let mut audio = AudioSettings {
+        music: 0.85,
+        ..default()
+    };
+    audio.step_volume(VolumeChannel::Music);
+    assert_eq!(audio.music, 1.0);
+    audio.step_volume(VolumeChannel::Music);
+    assert_eq!(audio.music, 0.0);
+    audio.step_volume(VolumeChannel::Music);
+    assert!((audio.music - 0.1).abs() < f32::EPSILON);
+}
+
+#[test]
+fn language_is_kept_in_the_settings_file() {
+    let settings = Settings {
+        language: Language::German,
+        ..default()
+    };
+    let contents = ron::to_string(&settings).unwrap();
+    let read: Settings = ron::from_str(&contents).unwrap();
+    assert_eq!(read.language, Language::German);
+
+    // files written before the language was stored start in English
+    let old: Settings = ron::from_str("(audio: (master: 0.5))").unwrap();
+    assert_eq!(old.language, Language::English);
+}
@@ -0,0 +1,36 @@
+use crate::arcade_game::GameState;
+use bevy::prelude::*;
+use components::*;
+use iyes_loopless::prelude::*;
+use systems::*;
+
+pub mod components;
+pub mod events;
+mod systems;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundAssets>()
+            .init_resource::<MusicPlayer>()
+            .add_event::<SfxEvent>()
+            .add_startup_system(setup)
+            .add_enter_system(GameState::MainMenu, play_menu_music)
+            .add_enter_system(GameState::InGame, play_game_music)
+            .add_enter_system(GameState::Pause, duck_music)
+            .add_system(queue_combat_sfx.run_in_state(GameState::InGame))
+            .add_system(play_sfx.after(queue_combat_sfx))
+            .add_system(update_music_volume);
+    }
+}
+
+const MENU_MUSIC_PATH: &str = "audio/music_main_menu.ogg";
+const GAME_MUSIC_PATH: &str = "audio/music_in_game.ogg";
+const FIRE_SFX_PATH: &str = "audio/sfx_fire.ogg";
+const EXPLOSION_SFX_PATH: &str = "audio/sfx_explosion.ogg";
+const TILE_DESTROYED_SFX_PATH: &str = "audio/sfx_tile_destroyed.ogg";
+const MENU_HOVER_SFX_PATH: &str = "audio/sfx_menu_hover.ogg";
+
+/// Music volume multiplier applied while the pause menu is open.
+const PAUSE_DUCKING: f32 = 0.3;
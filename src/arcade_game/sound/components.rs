@@ -0,0 +1,63 @@
+use crate::arcade_game::settings::AudioSettings;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    Fire,
+    Explosion,
+    TileDestroyed,
+    MenuHover,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicTrack {
+    MainMenu,
+    InGame,
+}
+
+pub struct SfxEvent(pub Sfx);
+
+#[derive(Resource, Default)]
+pub struct SoundAssets {
+    pub(crate) menu_music: Handle<AudioSource>,
+    pub(crate) game_music: Handle<AudioSource>,
+    pub(crate) fire: Handle<AudioSource>,
+    pub(crate) explosion: Handle<AudioSource>,
+    pub(crate) tile_destroyed: Handle<AudioSource>,
+    pub(crate) menu_hover: Handle<AudioSource>,
+}
+
+#[derive(Resource, Default)]
+pub struct MusicPlayer {
+    pub(crate) track: Option<MusicTrack>,
+    pub(crate) sink: Handle<AudioSink>,
+    pub(crate) ducked: bool,
+}
+
+impl SoundAssets {
+    pub fn music(&self, track: MusicTrack) -> Handle<AudioSource> {
+        match track {
+            MusicTrack::MainMenu => self.menu_music.clone(),
+            MusicTrack::InGame => self.game_music.clone(),
+        }
+    }
+
+    pub fn sfx(&self, sfx: Sfx) -> Handle<AudioSource> {
+        match sfx {
+            Sfx::Fire => self.fire.clone(),
+            Sfx::Explosion => self.explosion.clone(),
+            Sfx::TileDestroyed => self.tile_destroyed.clone(),
+            Sfx::MenuHover => self.menu_hover.clone(),
+        }
+    }
+}
+
+impl MusicPlayer {
+    pub fn volume(&self, settings: &AudioSettings) -> f32 {
+        if self.ducked {
+            settings.music_volume() * super::PAUSE_DUCKING
+        } else {
+            settings.music_volume()
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::arcade_game::combat::events::*;
+use crate::arcade_game::settings::{AudioSettings, Settings};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use super::components::*;
+
+pub fn setup(asset_server: Res<AssetServer>, mut sound_assets: ResMut<SoundAssets>) {
+    *sound_assets = SoundAssets {
+        menu_music: asset_server.load(super::MENU_MUSIC_PATH),
+        game_music: asset_server.load(super::GAME_MUSIC_PATH),
+        fire: asset_server.load(super::FIRE_SFX_PATH),
+        explosion: asset_server.load(super::EXPLOSION_SFX_PATH),
+        tile_destroyed: asset_server.load(super::TILE_DESTROYED_SFX_PATH),
+        menu_hover: asset_server.load(super::MENU_HOVER_SFX_PATH),
+    };
+}
+
+fn switch_music(
+    track: MusicTrack,
+    audio: &Audio,
+    audio_sinks: &Assets<AudioSink>,
+    sound_assets: &SoundAssets,
+    settings: &AudioSettings,
+    music_player: &mut MusicPlayer,
+) {
+    music_player.ducked = false;
+    if music_player.track == Some(track) {
+        return;
+    }
+    if let Some(sink) = audio_sinks.get(&music_player.sink) {
+        sink.stop();
+    }
+    let weak_sink = audio.play_with_settings(
+        sound_assets.music(track),
+        PlaybackSettings::LOOP.with_volume(settings.music_volume()),
+    );
+    music_player.sink = audio_sinks.get_handle(weak_sink);
+    music_player.track = Some(track);
+}
+
+pub fn play_menu_music(
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    sound_assets: Res<SoundAssets>,
+    settings: Res<Settings>,
+    mut music_player: ResMut<MusicPlayer>,
+) {
+    switch_music(
+        MusicTrack::MainMenu,
+        &audio,
+        &audio_sinks,
+        &sound_assets,
+        &settings.audio,
+        &mut music_player,
+    );
+}
+
+pub fn play_game_music(
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    sound_assets: Res<SoundAssets>,
+    settings: Res<Settings>,
+    mut music_player: ResMut<MusicPlayer>,
+) {
+    switch_music(
+        MusicTrack::InGame,
+        &audio,
+        &audio_sinks,
+        &sound_assets,
+        &settings.audio,
+        &mut music_player,
+    );
+}
+
+pub fn duck_music(mut music_player: ResMut<MusicPlayer>) {
+    music_player.ducked = true;
+}
+
+pub fn update_music_volume(
+    settings: Res<Settings>,
+    music_player: Res<MusicPlayer>,
+    audio_sinks: Res<Assets<AudioSink>>,
+) {
+    if !settings.is_changed() && !music_player.is_changed() {
+        return;
+    }
+    if let Some(sink) = audio_sinks.get(&music_player.sink) {
+        sink.set_volume(music_player.volume(&settings.audio));
+    }
+}
+
+pub fn queue_combat_sfx(
+    mut ev_fired: EventReader<ProjectileFiredEvent>,
+    mut ev_explosion: EventReader<ExplosionEvent>,
+    mut ev_tile_destroyed: EventReader<TileDestroyedEvent>,
+    mut ev_sfx: EventWriter<SfxEvent>,
+) {
+    ev_sfx.send_batch(ev_fired.iter().map(|_| SfxEvent(Sfx::Fire)));
+    ev_sfx.send_batch(ev_explosion.iter().map(|_| SfxEvent(Sfx::Explosion)));
+    ev_sfx.send_batch(
+        ev_tile_destroyed
+            .iter()
+            .map(|_| SfxEvent(Sfx::TileDestroyed)),
+    );
+}
+
+pub fn play_sfx(
+    mut ev_sfx: EventReader<SfxEvent>,
+    audio: Res<Audio>,
+    sound_assets: Res<SoundAssets>,
+    settings: Res<Settings>,
+) {
+    // a single blast can destroy a dozen tiles, play each effect once per frame
+    let effects: HashSet<Sfx> = ev_sfx.iter().map(|SfxEvent(sfx)| *sfx).collect();
+    for sfx in effects {
+        audio.play_with_settings(
+            sound_assets.sfx(sfx),
+            PlaybackSettings::ONCE.with_volume(settings.audio.sfx_volume()),
+        );
+    }
+}
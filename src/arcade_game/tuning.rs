@@ -0,0 +1,44 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use components::*;
+
+pub mod components;
+mod systems;
+
+/// Loads gameplay tuning from an asset and re-applies it whenever the file changes.
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Tuning>()
+            .add_asset_loader(TuningLoader)
+            .init_resource::<Tuning>()
+            .init_resource::<TuningHandle>()
+            .add_startup_system(systems::load_tuning)
+            .add_system(systems::apply_tuning_asset)
+            .add_system(systems::tune_players.after(systems::apply_tuning_asset));
+    }
+}
+
+const TUNING_PATH: &str = "gameplay.tuning.ron";
+
+struct TuningLoader;
+
+impl AssetLoader for TuningLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let tuning: Tuning = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(tuning));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
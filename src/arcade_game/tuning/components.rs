@@ -0,0 +1,36 @@
+use crate::arcade_game::combat::prelude::Projectile;
+use crate::arcade_game::player::prelude::PlayerBundle;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use serde::{Deserialize, Serialize};
+
+/// Gameplay numbers loaded from `gameplay.tuning.ron`.
+///
+/// Loaded as an asset and copied into a resource of the same type, so systems keep
+/// reading the last good values while the file is being edited.
+#[derive(Resource, TypeUuid, Debug, Clone, Serialize, Deserialize)]
+#[uuid = "6c3f1e2a-9d4b-4c7e-8a1f-3b5d7e9f2c41"]
+#[serde(default)]
+pub struct Tuning {
+    pub player_move_speed: f32,
+    pub projectile_speed: f32,
+    /// Degrees per second.
+    pub weapon_aim_speed: f32,
+    pub weapon_max_angle: f32,
+    pub weapon_min_angle: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            player_move_speed: PlayerBundle::DEFAULT_MOVE_SPEED,
+            projectile_speed: Projectile::DEFAULT_SPEED,
+            weapon_aim_speed: PlayerBundle::WEAPON_TRAJEC_ROT,
+            weapon_max_angle: PlayerBundle::WEAPON_TRAJEC_MAX_ROT,
+            weapon_min_angle: PlayerBundle::WEAPON_TRAJEC_MIN_ROT,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TuningHandle(pub(crate) Handle<Tuning>);
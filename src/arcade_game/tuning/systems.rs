@@ -0,0 +1,49 @@
+use crate::arcade_game::loading::components::GameAssets;
+use crate::arcade_game::physics::prelude::*;
+use crate::arcade_game::player::prelude::*;
+use bevy::prelude::*;
+
+use super::components::*;
+
+pub fn load_tuning(
+    asset_server: Res<AssetServer>,
+    mut tuning_handle: ResMut<TuningHandle>,
+    mut game_assets: ResMut<GameAssets>,
+) {
+    tuning_handle.0 = asset_server.load(super::TUNING_PATH);
+    game_assets.track(&tuning_handle.0);
+}
+
+/// Copies the tuning asset into the `Tuning` resource whenever it is loaded or edited.
+pub fn apply_tuning_asset(
+    mut ev_asset: EventReader<AssetEvent<Tuning>>,
+    tuning_handle: Res<TuningHandle>,
+    tuning_assets: Res<Assets<Tuning>>,
+    mut tuning: ResMut<Tuning>,
+) {
+    for event in ev_asset.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle }
+                if *handle == tuning_handle.0 =>
+            {
+                if let Some(loaded) = tuning_assets.get(handle) {
+                    *tuning = loaded.clone();
+                    info!("applied tuning from {}", super::TUNING_PATH);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pushes tuning into components that copy it at spawn time, leaving speeds set in LDtk.
+pub fn tune_players(
+    tuning: Res<Tuning>,
+    mut players: Query<(&mut Moveable, ChangeTrackers<Player>)>,
+) {
+    for (mut moveable, player_tracker) in &mut players {
+        if moveable.tuned && (tuning.is_changed() || player_tracker.is_added()) {
+            moveable.speed = tuning.player_move_speed;
+        }
+    }
+}
@@ -1,18 +1,39 @@
+use crate::arcade_game::loading::components::GameAssets;
+use crate::arcade_game::locale::components::Locale;
 use bevy::prelude::*;
 use kayak_ui::{prelude::*, widgets::KayakWidgets};
 
 mod components;
+pub mod load_menu;
+pub mod loading_screen;
 pub mod main_menu;
+pub mod menu;
+pub mod options_menu;
 pub mod pause_menu;
+#[cfg(test)]
+mod tests;
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UIAssets>()
+            .init_resource::<menu::MenuStack>()
+            .add_event::<menu::MenuActionEvent>()
+            .add_event::<menu::MenuFocusEvent>()
             .add_plugin(KayakContextPlugin)
             .add_plugin(KayakWidgets)
-            .add_startup_system(startup);
+            .add_startup_system(startup)
+            .add_system(menu::navigate_menu)
+            .add_system(menu::run_menu_actions.after(menu::navigate_menu))
+            .add_system(menu::relabel_menus.after(menu::run_menu_actions))
+            .add_system(menu::animate_menu)
+            .add_system(
+                menu::sync_menu_panel
+                    .after(menu::animate_menu)
+                    .after(menu::relabel_menus),
+            )
+            .add_system(select_font.before(menu::relabel_menus));
     }
 }
 
@@ -21,17 +42,36 @@ pub const PANEL_INDEX: usize = 0;
 pub const BUTTON_INDEX: usize = 2;
 pub const HOVER_BUTTON_INDEX: usize = 3;
 
+const PRIMARY_FONT_INDEX: usize = 0;
+const FALLBACK_FONT_INDEX: usize = 1;
+
+const PRIMARY_FONT_PATH: &str = "weiholmir.kayak_font";
+/// Used instead of the primary font when it lacks glyphs the current language needs.
+const FALLBACK_FONT_PATH: &str = "dejavu_sans.kayak_font";
+
+const PROGRESS_BACKGROUND_COLOR: Color = Color::rgb(0.15, 0.1, 0.1);
+const PROGRESS_FILL_COLOR: Color = Color::rgb(0.85, 0.65, 0.3);
+
 #[derive(Default, Resource)]
 pub struct UIAssets {
     images: Vec<Handle<Image>>,
+    fonts: Vec<Handle<KayakFont>>,
 }
 
 fn startup(
     mut font_mapping: ResMut<FontMapping>,
     asset_server: Res<AssetServer>,
     mut preload_resource: ResMut<UIAssets>,
+    mut game_assets: ResMut<GameAssets>,
 ) {
-    font_mapping.set_default(asset_server.load("weiholmir.kayak_font"));
+    preload_resource.fonts.extend(vec![
+        asset_server.load(PRIMARY_FONT_PATH),
+        asset_server.load(FALLBACK_FONT_PATH),
+    ]);
+    for font in &preload_resource.fonts {
+        game_assets.track(font);
+    }
+    font_mapping.set_default(preload_resource.fonts[PRIMARY_FONT_INDEX].clone());
 
     let panel_image = asset_server.load("main_menu/panel.png");
     let pause_icon = asset_server.load("main_menu/pause.png");
@@ -44,4 +84,33 @@ fn startup(
         button_image.clone(),
         button_image_hover.clone(),
     ]);
+    for image in &preload_resource.images {
+        game_assets.track(image);
+    }
+}
+
+/// Switches to the fallback font while the primary one can't draw every string of the
+/// current language.
+fn select_font(
+    mut ev_font: EventReader<AssetEvent<KayakFont>>,
+    locale: Res<Locale>,
+    fonts: Res<Assets<KayakFont>>,
+    ui_assets: Res<UIAssets>,
+    mut font_mapping: ResMut<FontMapping>,
+) {
+    if ev_font.iter().count() == 0 && !locale.is_changed() {
+        return;
+    }
+    let covers = |index: usize| match fonts.get(&ui_assets.fonts[index]) {
+        Some(font) => locale
+            .chars()
+            .all(|c| c.is_whitespace() || font.get_char_id(c).is_some()),
+        None => false,
+    };
+    let font = if covers(PRIMARY_FONT_INDEX) || !covers(FALLBACK_FONT_INDEX) {
+        PRIMARY_FONT_INDEX
+    } else {
+        FALLBACK_FONT_INDEX
+    };
+    font_mapping.set_default(ui_assets.fonts[font].clone());
 }
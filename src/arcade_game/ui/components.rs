@@ -1,14 +1,27 @@
+use super::menu::{Menu, MenuActionEvent, MenuFocusEvent};
 use super::UIAssets;
+use crate::arcade_game::sound::events::*;
 use bevy::prelude::*;
 use kayak_ui::{prelude::*, widgets::*};
 
 #[derive(Default, Clone, PartialEq, Component)]
 pub struct MenuButton {
     pub text: String,
+    /// Position in its menu, if it belongs to one; hovering it moves the menu focus there.
+    pub index: Option<usize>,
+    /// Picked by the next select key press.
+    pub focused: bool,
 }
 
 impl Widget for MenuButton {}
 
+/// Takes the place of Kayak's `ButtonState`, which only knows about the mouse.
+#[derive(Default, Clone, PartialEq, Component)]
+pub struct MenuButtonState {
+    pub hovering: bool,
+    pub focused: bool,
+}
+
 #[derive(Bundle)]
 pub struct MenuButtonBundle {
     pub button: MenuButton,
@@ -37,12 +50,20 @@ pub fn menu_button_render(
     mut commands: Commands,
     ui_assets: Res<UIAssets>,
     menu_button_query: Query<&MenuButton>,
-    state_query: Query<&ButtonState>,
+    mut state_query: Query<&mut MenuButtonState>,
 ) -> bool {
-    let state_entity =
-        widget_context.use_state(&mut commands, entity, ButtonState { hovering: false });
+    let menu_button = menu_button_query.get(entity).unwrap();
+    let state_entity = widget_context.use_state(
+        &mut commands,
+        entity,
+        MenuButtonState {
+            hovering: false,
+            focused: menu_button.focused,
+        },
+    );
 
-    let button_text = menu_button_query.get(entity).unwrap().text.clone();
+    let button_text = menu_button.text.clone();
+    let index = menu_button.index;
     let button_image = ui_assets.images[super::BUTTON_INDEX].clone();
     let button_image_hover = ui_assets.images[super::HOVER_BUTTON_INDEX].clone();
 
@@ -53,12 +74,19 @@ pub fn menu_button_render(
             Event,
             Entity,
         )>,
-              mut query: Query<&mut ButtonState>| {
+              mut query: Query<&mut MenuButtonState>,
+              mut ev_focus: EventWriter<MenuFocusEvent>,
+              mut ev_sfx: EventWriter<SfxEvent>| {
             if let Ok(mut button) = query.get_mut(state_entity) {
                 match event.event_type {
                     EventType::MouseIn(..) => {
                         event.stop_propagation();
-                        button.hovering = true;
+                        // menu items share one highlight between the mouse and the keys
+                        match index {
+                            Some(index) => ev_focus.send(MenuFocusEvent(index)),
+                            None => button.hovering = true,
+                        }
+                        ev_sfx.send(SfxEvent(Sfx::MenuHover));
                     }
                     EventType::MouseOut(..) => {
                         button.hovering = false;
@@ -70,8 +98,12 @@ pub fn menu_button_render(
         },
     );
 
-    if let Ok(button_state) = state_query.get(state_entity) {
-        let (button_image_handle, border) = if button_state.hovering {
+    if let Ok(mut button_state) = state_query.get_mut(state_entity) {
+        // the menu owns the focus, the state follows it
+        if button_state.focused != menu_button.focused {
+            button_state.focused = menu_button.focused;
+        }
+        let (button_image_handle, border) = if button_state.hovering || button_state.focused {
             (button_image_hover, Edge::all(8.0_f32))
         } else {
             (button_image, Edge::all(4.0_f32))
@@ -112,3 +144,191 @@ pub fn menu_button_render(
     }
     true
 }
+
+/// The top page of the menu stack: its title and one `MenuButton` per item.
+#[derive(Default, Clone, PartialEq, Component)]
+pub struct MenuPanel {
+    pub menu: Menu,
+    pub focus: usize,
+    /// How far the opening transition has got, from 0 to 1.
+    pub reveal: f32,
+}
+
+impl Widget for MenuPanel {}
+
+#[derive(Bundle)]
+pub struct MenuPanelBundle {
+    pub panel: MenuPanel,
+    pub styles: KStyle,
+    pub widget_name: WidgetName,
+}
+
+impl Default for MenuPanelBundle {
+    fn default() -> Self {
+        Self {
+            panel: Default::default(),
+            styles: KStyle {
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            },
+            widget_name: MenuPanel::default().get_name(),
+        }
+    }
+}
+
+pub fn menu_panel_render(
+    In((widget_context, entity)): In<(KayakWidgetContext, Entity)>,
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    menu_panel_query: Query<&MenuPanel>,
+) -> bool {
+    let panel = menu_panel_query.get(entity).unwrap().clone();
+    let offset = super::menu::transition_offset(panel.reveal);
+
+    let parent_id = Some(entity);
+    rsx! {
+        <ElementBundle
+            styles={KStyle {
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                padding: Edge::new(
+                    Units::Pixels(offset),
+                    Units::Pixels(0.0),
+                    Units::Pixels(0.0),
+                    Units::Pixels(0.0),
+                ).into(),
+                ..KStyle::default()
+            }}
+        >
+            <NinePatchBundle
+                nine_patch={NinePatch {
+                    handle: ui_assets.images[super::PANEL_INDEX].clone(),
+                    border: Edge::all(15.0),
+                }}
+                styles={KStyle {
+                    width: Units::Pixels(panel.menu.width).into(),
+                    height: Units::Pixels(panel.menu.height()).into(),
+                    left: Units::Stretch(1.0).into(),
+                    right: Units::Stretch(1.0).into(),
+                    top: Units::Stretch(1.0).into(),
+                    bottom: Units::Stretch(1.0).into(),
+                    padding: Edge::new(
+                        Units::Pixels(60.0),
+                        Units::Pixels(20.0),
+                        Units::Pixels(50.0),
+                        Units::Pixels(20.0),
+                    ).into(),
+                    row_between: Units::Pixels(20.0).into(),
+                    ..KStyle::default()
+                }}
+            >
+                <TextWidgetBundle
+                    text={TextProps {
+                        content: panel.menu.title.clone(),
+                        alignment: Alignment::Middle,
+                        size: 28.0,
+                        line_height: Some(60.0),
+                        ..Default::default()
+                    }}
+                    styles={KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        ..KStyle::default()
+                    }}
+                />
+                {panel.menu.items.iter().enumerate().for_each(|(index, item)| {
+                    let action = item.action.clone();
+                    let on_event = OnEvent::new(
+                        move |In((event_dispatcher_context, _, event, _entity)): In<(
+                            EventDispatcherContext,
+                            WidgetState,
+                            Event,
+                            Entity,
+                        )>,
+                              mut ev_action: EventWriter<MenuActionEvent>| {
+                            match event.event_type {
+                                EventType::Click(..) => {
+                                    ev_action.send(MenuActionEvent(action.clone()))
+                                }
+                                _ => {}
+                            }
+                            (event_dispatcher_context, event)
+                        },
+                    );
+                    let button = MenuButton {
+                        text: item.label.clone(),
+                        index: Some(index),
+                        focused: index == panel.focus,
+                    };
+                    constructor! {
+                        <MenuButtonBundle button={button} on_event={on_event} />
+                    }
+                })}
+            </NinePatchBundle>
+        </ElementBundle>
+    };
+    true
+}
+
+#[derive(Default, Clone, PartialEq, Component)]
+pub struct ProgressBar {
+    /// Filled share of the bar, from 0 to 1.
+    pub progress: f32,
+}
+
+impl Widget for ProgressBar {}
+
+#[derive(Bundle)]
+pub struct ProgressBarBundle {
+    pub bar: ProgressBar,
+    pub styles: KStyle,
+    pub widget_name: WidgetName,
+}
+
+impl Default for ProgressBarBundle {
+    fn default() -> Self {
+        Self {
+            bar: Default::default(),
+            styles: KStyle {
+                width: Units::Stretch(1.0).into(),
+                height: Units::Pixels(24.0).into(),
+                ..Default::default()
+            },
+            widget_name: ProgressBar::default().get_name(),
+        }
+    }
+}
+
+pub fn progress_bar_render(
+    In((widget_context, entity)): In<(KayakWidgetContext, Entity)>,
+    mut commands: Commands,
+    progress_bar_query: Query<&ProgressBar>,
+) -> bool {
+    let progress = progress_bar_query
+        .get(entity)
+        .unwrap()
+        .progress
+        .clamp(0.0, 1.0);
+
+    let parent_id = Some(entity);
+    rsx! {
+        <BackgroundBundle
+            styles={KStyle {
+                background_color: super::PROGRESS_BACKGROUND_COLOR.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..KStyle::default()
+            }}
+        >
+            <BackgroundBundle
+                styles={KStyle {
+                    background_color: super::PROGRESS_FILL_COLOR.into(),
+                    width: Units::Percentage(100.0 * progress).into(),
+                    height: Units::Stretch(1.0).into(),
+                    ..KStyle::default()
+                }}
+            />
+        </BackgroundBundle>
+    };
+    true
+}
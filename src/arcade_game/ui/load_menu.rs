@@ -0,0 +1,39 @@
+use super::menu::*;
+use crate::arcade_game::locale::components::Locale;
+use crate::arcade_game::save;
+
+fn slot_text(
+    locale: &Locale,
+    slot: usize,
+    info: &Option<save::components::SaveSlotInfo>,
+) -> String {
+    let number = (slot + 1).to_string();
+    match info {
+        Some(info) => locale.format(
+            "menu.load.slot",
+            &[("slot", number), ("level", info.level.clone())],
+        ),
+        None => locale.format("menu.load.empty_slot", &[("slot", number)]),
+    }
+}
+
+/// Submenu of the main menu listing the save slots.
+pub fn menu(locale: &Locale) -> Menu {
+    let mut items: Vec<MenuItem> = save::list_slots()
+        .iter()
+        .enumerate()
+        .map(|(slot, info)| {
+            let action = match info {
+                Some(_) => MenuAction::Continue(slot),
+                None => MenuAction::None,
+            };
+            MenuItem::new(slot_text(locale, slot, info), action)
+        })
+        .collect();
+    items.push(MenuItem::new(locale.get("menu.back"), MenuAction::Back));
+    Menu {
+        title: locale.get("menu.load.title"),
+        width: 520.0,
+        items,
+    }
+}
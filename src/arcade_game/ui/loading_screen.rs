@@ -0,0 +1,171 @@
+use super::{components::*, *};
+use crate::arcade_game::locale::components::Locale;
+use crate::arcade_game::{self, loading::components::LoadingProgress};
+use kayak_ui::prelude::widgets::*;
+
+/// Marks the root of the loading and error screens, which can exist next to a menu
+/// for a frame while states change.
+#[derive(Component)]
+pub struct LoadingScreen;
+
+pub fn spawn_screen(mut commands: Commands, ui_assets: Res<UIAssets>, locale: Res<Locale>) {
+    let mut widget_context = KayakRootContext::new();
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    widget_context.add_widget_data::<ProgressBar, EmptyState>();
+    widget_context.add_widget_system(
+        ProgressBar::default().get_name(),
+        widget_update::<ProgressBar, EmptyState>,
+        progress_bar_render,
+    );
+
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <NinePatchBundle
+                nine_patch={NinePatch {
+                    handle: ui_assets.images[super::PANEL_INDEX].clone(),
+                    border: Edge::all(15.0),
+                }}
+                styles={KStyle {
+                    width: Units::Pixels(420.0).into(),
+                    height: Units::Pixels(200.0).into(),
+                    left: Units::Stretch(1.0).into(),
+                    right: Units::Stretch(1.0).into(),
+                    top: Units::Stretch(1.0).into(),
+                    bottom: Units::Stretch(1.0).into(),
+                    padding: Edge::new(
+                        Units::Pixels(40.0),
+                        Units::Pixels(40.0),
+                        Units::Pixels(40.0),
+                        Units::Pixels(40.0),
+                    ).into(),
+                    row_between: Units::Pixels(20.0).into(),
+                    ..KStyle::default()
+                }}
+            >
+                <TextWidgetBundle
+                    text={TextProps {
+                        content: locale.get("loading.title"),
+                        alignment: Alignment::Middle,
+                        size: 28.0,
+                        line_height: Some(60.0),
+                        ..Default::default()
+                    }}
+                    styles={KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        ..KStyle::default()
+                    }}
+                />
+                <ProgressBarBundle />
+            </NinePatchBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((UICameraBundle::new(widget_context), LoadingScreen));
+}
+
+pub fn spawn_error_screen(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    progress: Res<LoadingProgress>,
+    locale: Res<Locale>,
+) {
+    let mut widget_context = menu::widget_context();
+
+    let handle_click_main_menu = OnEvent::new(
+        move |In((event_dispatcher_context, _, event, _entity)): In<(
+            EventDispatcherContext,
+            WidgetState,
+            Event,
+            Entity,
+        )>,
+              commands: Commands| {
+            match event.event_type {
+                EventType::Click(..) => arcade_game::quit_to_main_menu(commands),
+                _ => {}
+            }
+            (event_dispatcher_context, event)
+        },
+    );
+
+    let missing = progress.missing.clone();
+    let main_menu_text = locale.get("menu.main_menu");
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <NinePatchBundle
+                nine_patch={NinePatch {
+                    handle: ui_assets.images[super::PANEL_INDEX].clone(),
+                    border: Edge::all(15.0),
+                }}
+                styles={KStyle {
+                    width: Units::Pixels(520.0).into(),
+                    height: Units::Pixels(480.0).into(),
+                    left: Units::Stretch(1.0).into(),
+                    right: Units::Stretch(1.0).into(),
+                    top: Units::Stretch(1.0).into(),
+                    bottom: Units::Stretch(1.0).into(),
+                    padding: Edge::new(
+                        Units::Pixels(60.0),
+                        Units::Pixels(20.0),
+                        Units::Pixels(50.0),
+                        Units::Pixels(20.0),
+                    ).into(),
+                    row_between: Units::Pixels(10.0).into(),
+                    ..KStyle::default()
+                }}
+            >
+                <TextWidgetBundle
+                    text={TextProps {
+                        content: locale.get("loading.missing_assets"),
+                        alignment: Alignment::Middle,
+                        size: 28.0,
+                        line_height: Some(60.0),
+                        ..Default::default()
+                    }}
+                    styles={KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        ..KStyle::default()
+                    }}
+                />
+                {missing.iter().for_each(|path| {
+                    constructor! {
+                        <TextWidgetBundle
+                            text={TextProps {
+                                content: path.clone(),
+                                alignment: Alignment::Middle,
+                                size: 18.0,
+                                ..Default::default()
+                            }}
+                            styles={KStyle {
+                                width: Units::Stretch(1.0).into(),
+                                ..KStyle::default()
+                            }}
+                        />
+                    }
+                })}
+                <MenuButtonBundle
+                    button={MenuButton { text: main_menu_text, ..Default::default() }}
+                    on_event={handle_click_main_menu}
+                />
+            </NinePatchBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((UICameraBundle::new(widget_context), LoadingScreen));
+}
+
+pub fn despawn_screen(screen: Query<Entity, With<LoadingScreen>>, mut commands: Commands) {
+    for entity in &screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_progress_bar(progress: Res<LoadingProgress>, mut bars: Query<&mut ProgressBar>) {
+    let fraction = progress.fraction();
+    for mut bar in &mut bars {
+        if bar.progress != fraction {
+            bar.progress = fraction;
+        }
+    }
+}
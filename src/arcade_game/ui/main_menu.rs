@@ -1,112 +1,56 @@
-use super::{components::*, *};
-use crate::arcade_game::{self, SetupSceneEvent};
-use bevy::app::AppExit;
-use kayak_ui::prelude::widgets::*;
+use super::menu::*;
+use crate::arcade_game::locale::components::Locale;
+use crate::arcade_game::settings::Settings;
+use crate::arcade_game::{save, GameMode};
+use bevy::prelude::*;
 
-pub fn spawn_menu(mut commands: Commands, ui_assets: Res<UIAssets>) {
-    let mut widget_context = KayakRootContext::new();
-    widget_context.add_plugin(KayakWidgetsContextPlugin);
-    widget_context.add_widget_data::<MenuButton, ButtonState>();
-    widget_context.add_widget_system(
-        MenuButton::default().get_name(),
-        widget_update::<MenuButton, ButtonState>,
-        menu_button_render,
-    );
-
-    let handle_click_close = OnEvent::new(
-        move |In((event_dispatcher_context, _, event, _entity)): In<(
-            EventDispatcherContext,
-            WidgetState,
-            Event,
-            Entity,
-        )>,
-              exit: EventWriter<AppExit>| {
-            match event.event_type {
-                EventType::Click(..) => arcade_game::quit_game(exit),
-                _ => {}
-            }
-            (event_dispatcher_context, event)
-        },
-    );
-
-    let handle_click_play = OnEvent::new(
-        move |In((event_dispatcher_context, _, event, _entity)): In<(
-            EventDispatcherContext,
-            WidgetState,
-            Event,
-            Entity,
-        )>,
-              commands: Commands,
-              ev_writer_setup: EventWriter<SetupSceneEvent>| {
-            match event.event_type {
-                EventType::Click(..) => arcade_game::start_game(commands, ev_writer_setup),
-                _ => {}
-            }
-            (event_dispatcher_context, event)
-        },
-    );
-
-    let parent_id = None;
-    rsx! {
-        <KayakAppBundle>
-            <NinePatchBundle
-                nine_patch={NinePatch {
-                    handle: ui_assets.images[super::PANEL_INDEX].clone(),
-                    border: Edge::all(15.0),
-                }}
-                styles={KStyle {
-                    width: Units::Pixels(700.0).into(),
-                    height: Units::Pixels(400.0).into(),
-                    left: Units::Stretch(1.0).into(),
-                    right: Units::Stretch(1.0).into(),
-                    top: Units::Stretch(1.0).into(),
-                    bottom: Units::Stretch(1.0).into(),
-                    padding: Edge::new(
-                        Units::Pixels(60.0),
-                        Units::Pixels(20.0),
-                        Units::Pixels(50.0),
-                        Units::Pixels(20.0),
-                    ).into(),
-                    row_between: Units::Pixels(20.0).into(),
-                    ..KStyle::default()
-                }}
-            >
-                <TextWidgetBundle
-                    text={TextProps {
-                        content: "The Adventures of Gunther the Pig".into(),
-                        alignment: Alignment::Middle,
-                        size: 28.0,
-                        line_height: Some(60.0),
-                        ..Default::default()
-                    }}
-                    styles={KStyle {
-                        width: Units::Stretch(1.0).into(),
-                        ..KStyle::default()
-                    }}
-                />
-                <MenuButtonBundle
-                    button={MenuButton { text: "Play".into() }}
-                    on_event={handle_click_play}
-                />
-                <MenuButtonBundle
-                    button={MenuButton { text: "Options".into() }}
-                />
-                <MenuButtonBundle
-                    button={MenuButton { text: "Quit".into() }}
-                    on_event={handle_click_close}
-                />
-            </NinePatchBundle>
-        </KayakAppBundle>
-    };
-
-    commands.spawn(UICameraBundle::new(widget_context));
+pub fn menu(locale: &Locale) -> Menu {
+    let mut items = Vec::new();
+    if let Some(slot) = save::latest_slot() {
+        items.push(MenuItem::new(
+            locale.get("menu.continue"),
+            MenuAction::Continue(slot),
+        ));
+    }
+    items.extend([
+        MenuItem::new(
+            locale.get("menu.play"),
+            MenuAction::StartMode(GameMode::Arcade),
+        ),
+        MenuItem::new(locale.get("menu.new_game"), MenuAction::NewGame),
+        MenuItem::new(
+            locale.get("menu.artillery"),
+            MenuAction::StartMode(GameMode::Artillery),
+        ),
+        MenuItem::new(
+            locale.get("menu.versus"),
+            MenuAction::StartMode(GameMode::Versus),
+        ),
+        MenuItem::new(locale.get("menu.load"), MenuAction::Open(MenuId::Load)),
+        MenuItem::new(
+            locale.get("menu.options"),
+            MenuAction::Open(MenuId::Options),
+        ),
+        MenuItem::new(locale.get("menu.quit"), MenuAction::Quit),
+    ]);
+    Menu {
+        title: locale.get("menu.main.title"),
+        width: 700.0,
+        items,
+    }
 }
 
-pub fn despawn_menu(root_context: Query<Entity, With<KayakRootContext>>, mut commands: Commands) {
-    if root_context.is_empty() {
-        dbg!("kayak root context empty");
-        return;
-    }
-    let root_context = root_context.single();
-    commands.entity(root_context).despawn_recursive();
+pub fn spawn_menu(
+    mut commands: Commands,
+    mut menu_stack: ResMut<MenuStack>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+) {
+    open_menu(
+        MenuId::Main,
+        &mut commands,
+        &mut menu_stack,
+        &locale,
+        &settings,
+    );
 }
@@ -0,0 +1,351 @@
+use super::components::*;
+use super::*;
+use crate::arcade_game::locale::components::{Language, Locale};
+use crate::arcade_game::settings::{Settings, VolumeChannel};
+use crate::arcade_game::sound::events::*;
+use crate::arcade_game::{self, physics::events::UnfreezePhysicsEvent, GameMode};
+use bevy::app::AppExit;
+use kayak_ui::prelude::widgets::*;
+
+use super::{load_menu, main_menu, options_menu, pause_menu};
+
+/// Seconds a menu takes to slide into place.
+const TRANSITION_TIME: f32 = 0.25;
+/// How far below its resting place a menu starts sliding in, in pixels.
+const TRANSITION_DISTANCE: f32 = 120.0;
+
+const UP_KEYS: [KeyCode; 2] = [KeyCode::Up, KeyCode::W];
+const DOWN_KEYS: [KeyCode; 2] = [KeyCode::Down, KeyCode::S];
+const SELECT_KEYS: [KeyCode; 2] = [KeyCode::Return, KeyCode::Space];
+const BACK_KEYS: [KeyCode; 2] = [KeyCode::Escape, KeyCode::Back];
+
+/// What picking a menu item does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuAction {
+    /// Shown, but does nothing.
+    None,
+    StartMode(GameMode),
+    /// Starts a new arcade run with an intact map.
+    NewGame,
+    Continue(usize),
+    /// Opens a submenu on top of the current one.
+    Open(MenuId),
+    /// Returns to the menu below.
+    Back,
+    /// Switches the UI to the next language and saves the settings.
+    NextLanguage,
+    /// Turns a volume channel up, wrapping around to silent, and saves the settings.
+    NextVolume(VolumeChannel),
+    Resume,
+    QuitToMainMenu,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuItem {
+    pub label: String,
+    pub action: MenuAction,
+}
+
+impl MenuItem {
+    pub fn new(label: impl Into<String>, action: MenuAction) -> Self {
+        Self {
+            label: label.into(),
+            action,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Menu {
+    pub title: String,
+    pub width: f32,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    /// Room for the title, the padding and every item.
+    pub fn height(&self) -> f32 {
+        160.0 + 80.0 * self.items.len() as f32
+    }
+}
+
+/// Every menu that can be opened. Menus are built when opened so they show current data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuId {
+    Main,
+    Load,
+    Options,
+    Pause,
+}
+
+impl MenuId {
+    fn build(self, locale: &Locale, settings: &Settings) -> Menu {
+        match self {
+            MenuId::Main => main_menu::menu(locale),
+            MenuId::Load => load_menu::menu(locale),
+            MenuId::Options => options_menu::menu(locale, &settings.audio),
+            MenuId::Pause => pause_menu::menu(locale),
+        }
+    }
+}
+
+struct MenuPage {
+    id: MenuId,
+    menu: Menu,
+    focus: usize,
+    /// Seconds since the page was opened, for its transition.
+    age: f32,
+}
+
+impl MenuPage {
+    fn new(id: MenuId, locale: &Locale, settings: &Settings) -> Self {
+        Self {
+            id,
+            menu: id.build(locale, settings),
+            focus: 0,
+            age: 0.0,
+        }
+    }
+
+    fn panel(&self) -> MenuPanel {
+        MenuPanel {
+            menu: self.menu.clone(),
+            focus: self.focus,
+            reveal: (self.age / TRANSITION_TIME).min(1.0),
+        }
+    }
+}
+
+/// The open menu and the submenus stacked on top of it; only the top one is shown.
+#[derive(Resource, Default)]
+pub struct MenuStack {
+    pages: Vec<MenuPage>,
+}
+
+impl MenuStack {
+    fn top(&self) -> Option<&MenuPage> {
+        self.pages.last()
+    }
+
+    fn top_mut(&mut self) -> Option<&mut MenuPage> {
+        self.pages.last_mut()
+    }
+
+    /// Whether a submenu is open, whose Back the game must not act on as well.
+    pub fn in_submenu(&self) -> bool {
+        self.pages.len() > 1
+    }
+}
+
+pub struct MenuActionEvent(pub MenuAction);
+
+/// Moves the focus of the open menu to an item, e.g. when the mouse enters it.
+pub struct MenuFocusEvent(pub usize);
+
+/// Marks the Kayak root of the open menu.
+#[derive(Component)]
+pub struct MenuRoot;
+
+/// A Kayak root with the widgets every screen built from menu buttons needs.
+pub fn widget_context() -> KayakRootContext {
+    let mut widget_context = KayakRootContext::new();
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    widget_context.add_widget_data::<MenuButton, MenuButtonState>();
+    widget_context.add_widget_system(
+        MenuButton::default().get_name(),
+        widget_update::<MenuButton, MenuButtonState>,
+        menu_button_render,
+    );
+    widget_context.add_widget_data::<MenuPanel, EmptyState>();
+    widget_context.add_widget_system(
+        MenuPanel::default().get_name(),
+        widget_update::<MenuPanel, EmptyState>,
+        menu_panel_render,
+    );
+    widget_context
+}
+
+/// Replaces whatever menu is open with `id`.
+pub fn open_menu(
+    id: MenuId,
+    commands: &mut Commands,
+    menu_stack: &mut MenuStack,
+    locale: &Locale,
+    settings: &Settings,
+) {
+    menu_stack.pages = vec![MenuPage::new(id, locale, settings)];
+    let panel = menu_stack.pages[0].panel();
+
+    let mut widget_context = widget_context();
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <MenuPanelBundle panel={panel} />
+        </KayakAppBundle>
+    };
+
+    commands.spawn((UICameraBundle::new(widget_context), MenuRoot));
+}
+
+pub fn despawn_menu(
+    root: Query<Entity, With<MenuRoot>>,
+    mut commands: Commands,
+    mut menu_stack: ResMut<MenuStack>,
+) {
+    for entity in &root {
+        commands.entity(entity).despawn_recursive();
+    }
+    menu_stack.pages.clear();
+}
+
+fn just_pressed(
+    keys: &[KeyCode],
+    button: GamepadButtonType,
+    input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_input: &Input<GamepadButton>,
+) -> bool {
+    input.any_just_pressed(keys.iter().copied())
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button)))
+}
+
+/// Moves the focus with the mouse, the arrow keys or the d-pad, wrapping around at either
+/// end, and picks the focused item.
+pub fn navigate_menu(
+    input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut menu_stack: ResMut<MenuStack>,
+    mut ev_focus: EventReader<MenuFocusEvent>,
+    mut ev_action: EventWriter<MenuActionEvent>,
+    mut ev_sfx: EventWriter<SfxEvent>,
+) {
+    let page = match menu_stack.top() {
+        Some(page) => page,
+        None => return,
+    };
+    let pressed =
+        |keys: &[KeyCode], button| just_pressed(keys, button, &input, &gamepads, &gamepad_input);
+
+    let count = page.menu.items.len();
+    if count == 0 {
+        return;
+    }
+    let mut focus = page.focus;
+    for MenuFocusEvent(index) in ev_focus.iter() {
+        if *index < count {
+            focus = *index;
+        }
+    }
+    if pressed(&UP_KEYS, GamepadButtonType::DPadUp) {
+        focus = (focus + count - 1) % count;
+        ev_sfx.send(SfxEvent(Sfx::MenuHover));
+    }
+    if pressed(&DOWN_KEYS, GamepadButtonType::DPadDown) {
+        focus = (focus + 1) % count;
+        ev_sfx.send(SfxEvent(Sfx::MenuHover));
+    }
+    if pressed(&SELECT_KEYS, GamepadButtonType::South) {
+        ev_action.send(MenuActionEvent(page.menu.items[focus].action.clone()));
+    }
+    // backing out of a top level menu is left to the game, e.g. Escape resumes from pause
+    if menu_stack.in_submenu() && pressed(&BACK_KEYS, GamepadButtonType::East) {
+        ev_action.send(MenuActionEvent(MenuAction::Back));
+    }
+
+    // only touch the stack on a move so the panel isn't rebuilt every frame
+    if focus != page.focus {
+        if let Some(page) = menu_stack.top_mut() {
+            page.focus = focus;
+        }
+    }
+}
+
+pub fn run_menu_actions(
+    mut ev_action: EventReader<MenuActionEvent>,
+    mut menu_stack: ResMut<MenuStack>,
+    mut language: ResMut<Language>,
+    locale: Res<Locale>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    ev_unfreeze: EventWriter<UnfreezePhysicsEvent>,
+    exit: EventWriter<AppExit>,
+) {
+    // a single pick per frame, the others would act on a menu that is already gone
+    let action = match ev_action.iter().last() {
+        Some(MenuActionEvent(action)) => action.clone(),
+        None => return,
+    };
+    match action {
+        MenuAction::None => {}
+        MenuAction::StartMode(mode) => arcade_game::start_mode(mode, commands),
+        MenuAction::NewGame => arcade_game::new_game(commands),
+        MenuAction::Continue(slot) => arcade_game::continue_game(slot, commands),
+        MenuAction::Open(id) => menu_stack.pages.push(MenuPage::new(id, &locale, &settings)),
+        MenuAction::Back => {
+            if menu_stack.pages.len() > 1 {
+                menu_stack.pages.pop();
+            }
+        }
+        MenuAction::NextLanguage => {
+            *language = language.next();
+            settings.language = *language;
+            settings.save();
+        }
+        MenuAction::NextVolume(channel) => {
+            settings.audio.step_volume(channel);
+            settings.save();
+        }
+        MenuAction::Resume => arcade_game::resume_game(&mut commands, ev_unfreeze),
+        MenuAction::QuitToMainMenu => arcade_game::quit_to_main_menu(commands),
+        MenuAction::Quit => arcade_game::quit_game(exit),
+    }
+}
+
+/// Rebuilds the open menus in a new language or with changed settings, keeping their focus.
+pub fn relabel_menus(
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+    mut menu_stack: ResMut<MenuStack>,
+) {
+    if !(locale.is_changed() || settings.is_changed()) || menu_stack.pages.is_empty() {
+        return;
+    }
+    for page in &mut menu_stack.pages {
+        page.menu = page.id.build(&locale, &settings);
+    }
+}
+
+pub fn animate_menu(time: Res<Time>, mut menu_stack: ResMut<MenuStack>) {
+    let animating = menu_stack
+        .top()
+        .map_or(false, |page| page.age < TRANSITION_TIME);
+    if animating {
+        if let Some(page) = menu_stack.top_mut() {
+            page.age += time.delta_seconds();
+        }
+    }
+}
+
+/// Re-renders the menu panel whenever the top page, its focus or its transition changes.
+pub fn sync_menu_panel(menu_stack: Res<MenuStack>, mut panels: Query<&mut MenuPanel>) {
+    if !menu_stack.is_changed() {
+        return;
+    }
+    let panel = match menu_stack.top() {
+        Some(page) => page.panel(),
+        None => return,
+    };
+    for mut menu_panel in &mut panels {
+        if *menu_panel != panel {
+            *menu_panel = panel.clone();
+        }
+    }
+}
+
+pub(super) fn transition_offset(reveal: f32) -> f32 {
+    (1.0 - reveal).powi(2) * TRANSITION_DISTANCE
+}
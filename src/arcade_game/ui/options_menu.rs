@@ -0,0 +1,29 @@
+use super::menu::*;
+use crate::arcade_game::locale::components::Locale;
+use crate::arcade_game::settings::{AudioSettings, VolumeChannel};
+
+/// Submenu of the main and pause menus.
+pub fn menu(locale: &Locale, audio: &AudioSettings) -> Menu {
+    let language = locale.language().native_name().to_string();
+    let volume_item = |key, channel| {
+        let percent = (audio.volume(channel) * 100.0).round().to_string();
+        MenuItem::new(
+            locale.format(key, &[("volume", percent)]),
+            MenuAction::NextVolume(channel),
+        )
+    };
+    Menu {
+        title: locale.get("menu.options.title"),
+        width: 520.0,
+        items: vec![
+            MenuItem::new(
+                locale.format("menu.options.language", &[("language", language)]),
+                MenuAction::NextLanguage,
+            ),
+            volume_item("menu.options.master_volume", VolumeChannel::Master),
+            volume_item("menu.options.music_volume", VolumeChannel::Music),
+            volume_item("menu.options.sfx_volume", VolumeChannel::Sfx),
+            MenuItem::new(locale.get("menu.back"), MenuAction::Back),
+        ],
+    }
+}
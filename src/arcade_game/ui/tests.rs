@@ -0,0 +1,41 @@
+use bevy::utils::{HashMap, HashSet};
+use std::{fs, path::Path};
+
+use super::{FALLBACK_FONT_PATH, PRIMARY_FONT_PATH};
+
+const ASSET_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets");
+
+/// The characters a `.kayak_font` has glyphs for; its JSON reads as a RON map.
+fn font_chars(path: &str) -> HashSet<char> {
+    let text = fs::read_to_string(Path::new(ASSET_DIR).join(path)).unwrap();
+    let font: HashMap<String, ron::Value> = ron::from_str(&text).unwrap();
+    let glyphs: Vec<HashMap<String, ron::Value>> = font["glyphs"].clone().into_rust().unwrap();
+    glyphs
+        .into_iter()
+        .filter_map(|glyph| char::from_u32(glyph["unicode"].clone().into_rust().ok()?))
+        .collect()
+}
+
+#[test]
+fn every_language_has_a_font_for_all_its_strings() {
+    let fonts = [
+        font_chars(PRIMARY_FONT_PATH),
+        font_chars(FALLBACK_FONT_PATH),
+    ];
+    for entry in fs::read_dir(Path::new(ASSET_DIR).join("lang")).unwrap() {
+        let path = entry.unwrap().path();
+        let strings: HashMap<String, String> =
+            ron::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let covered = fonts.iter().any(|font| {
+            strings
+                .values()
+                .flat_map(|string| string.chars())
+                .all(|c| c.is_whitespace() || font.contains(&c))
+        });
+        assert!(
+            covered,
+            "no font can draw every string of {}",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::arcade_game::map::TILE_SIZE;
+use crate::arcade_game::{versus_mode, GameState};
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use components::{CameraLayout, RoundEnd, VersusScore};
+use iyes_loopless::prelude::*;
+pub use systems::{despawn_player_cameras, setup_players};
+
+pub mod components;
+mod systems;
+
+/// Two players on one keyboard: a second player joins next to the first, and the camera
+/// either frames both or splits the screen.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraLayout>()
+            .init_resource::<VersusScore>()
+            .add_system(
+                systems::join_player_two
+                    .run_in_state(GameState::InGame)
+                    .run_if(versus_mode),
+            )
+            .add_system(
+                systems::toggle_camera_layout
+                    .run_in_state(GameState::InGame)
+                    .run_if(versus_mode),
+            )
+            .add_system(
+                systems::end_round
+                    .run_in_state(GameState::InGame)
+                    .run_if(versus_mode),
+            )
+            .add_system(
+                systems::start_next_round
+                    .run_in_state(GameState::InGame)
+                    .run_if_resource_exists::<RoundEnd>(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                systems::frame_players
+                    .run_in_state(GameState::InGame)
+                    .run_if(versus_mode)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+const LAYOUT_KEY: KeyCode = KeyCode::Tab;
+const PLAYER_TWO_OFFSET: f32 = 3.0 * TILE_SIZE.x;
+const PLAYER_TWO_TINT: Color = Color::rgb(0.7, 0.8, 1.0);
+/// Space kept around the players when the shared camera zooms out, in pixels.
+const FRAME_MARGIN: f32 = 32.0;
+/// Seconds between a round being decided and the next one starting.
+const ROUND_END_TIME: f32 = 2.0;
@@ -0,0 +1,27 @@
+use crate::arcade_game::player::prelude::PlayerId;
+use bevy::prelude::*;
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CameraLayout {
+    /// One camera zooming out to keep both players in view.
+    #[default]
+    Shared,
+    /// Each player gets half of the window.
+    Split,
+}
+
+/// Extra camera following a player other than player one, who uses the main camera.
+#[derive(Component)]
+pub struct PlayerCamera(pub PlayerId);
+
+/// Rounds each player has won since the match was started from the menu.
+#[derive(Resource, Default, Debug)]
+pub struct VersusScore {
+    pub wins: [u32; 2],
+}
+
+/// Set once a round has been decided; the next one starts when the timer runs out.
+#[derive(Resource)]
+pub struct RoundEnd {
+    pub timer: Timer,
+}
@@ -0,0 +1,225 @@
+use crate::arcade_game::character::CharacterBundle;
+use crate::arcade_game::combat::events::CharacterDiedEvent;
+use crate::arcade_game::combat::prelude::FriendlyFire;
+use crate::arcade_game::physics::prelude::*;
+use crate::arcade_game::player::prelude::*;
+use crate::arcade_game::{self, CameraScale, GameMode, MainCamera};
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy_ecs_ldtk::prelude::*;
+
+use super::components::*;
+
+/// Hands out keys, friendly fire and cameras for the mode the scene starts in.
+pub fn setup_players(
+    mode: Res<GameMode>,
+    camera_scale: Res<CameraScale>,
+    main_camera: Query<&Camera, With<MainCamera>>,
+    mut bindings: ResMut<PlayerBindings>,
+    mut friendly_fire: ResMut<FriendlyFire>,
+    mut commands: Commands,
+) {
+    commands.remove_resource::<RoundEnd>();
+    // in artillery whoever holds the keys has to be able to hit the other teams
+    friendly_fire.0 = *mode != GameMode::Arcade;
+    if *mode != GameMode::Versus {
+        *bindings = PlayerBindings::default();
+        return;
+    }
+    bindings.0 = vec![Bindings::left_hand(), Bindings::right_hand()];
+    // draws wherever the main camera does, the window or the pixel perfect image
+    let target = main_camera
+        .get_single()
+        .map(|camera| camera.target.clone())
+        .unwrap_or_default();
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                priority: 1,
+                is_active: false,
+                target,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 999.0),
+            projection: OrthographicProjection {
+                scale: camera_scale.0,
+                ..default()
+            },
+            ..default()
+        },
+        PlayerCamera(PlayerId::TWO),
+        Name::from("Player Two Camera"),
+    ));
+}
+
+pub fn despawn_player_cameras(cameras: Query<Entity, With<PlayerCamera>>, mut commands: Commands) {
+    for camera in &cameras {
+        commands.entity(camera).despawn_recursive();
+    }
+}
+
+/// Spawns player two next to player one, built from the same LDtk entity.
+pub fn join_player_two(
+    players: Query<
+        (
+            &PlayerId,
+            &Parent,
+            &Transform,
+            &EntityInstance,
+            &TextureAtlasSprite,
+            &Handle<TextureAtlas>,
+        ),
+        Added<PlayerId>,
+    >,
+    mut commands: Commands,
+) {
+    for (player_id, parent, transform, entity_instance, sprite, texture_atlas) in &players {
+        if *player_id != PlayerId::ONE {
+            continue;
+        }
+        let mut character_bundle = CharacterBundle::from(entity_instance.clone());
+        character_bundle.collider_bundle.collision_groups =
+            DynamicColliderBundle::player_collision_groups(PlayerId::TWO.0);
+        let sprite_bundle = SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                color: super::PLAYER_TWO_TINT,
+                ..sprite.clone()
+            },
+            texture_atlas: texture_atlas.clone(),
+            transform: Transform {
+                translation: transform.translation + super::PLAYER_TWO_OFFSET * Vec3::X,
+                ..*transform
+            },
+            ..default()
+        };
+        let player_two = commands
+            .spawn(PlayerBundle::new(
+                PlayerId::TWO,
+                sprite_bundle,
+                character_bundle,
+            ))
+            .insert(Name::from("Player Two"))
+            .id();
+        commands.entity(parent.get()).add_child(player_two);
+    }
+}
+
+/// Decides the round once a player has died: the other one wins it, or nobody if both
+/// went down together.
+pub fn end_round(
+    mut ev_died: EventReader<CharacterDiedEvent>,
+    round_end: Option<Res<RoundEnd>>,
+    mut score: ResMut<VersusScore>,
+    mut commands: Commands,
+) {
+    let died: Vec<PlayerId> = ev_died.iter().filter_map(|died| died.player).collect();
+    if died.is_empty() || round_end.is_some() {
+        return;
+    }
+    let winner = [PlayerId::ONE, PlayerId::TWO]
+        .into_iter()
+        .find(|player_id| !died.contains(player_id));
+    match winner {
+        Some(winner) => {
+            score.wins[winner.0] += 1;
+            info!(
+                "player {} wins the round, {} - {}",
+                winner.0 + 1,
+                score.wins[0],
+                score.wins[1]
+            );
+        }
+        None => info!("the round is a draw"),
+    }
+    commands.insert_resource(RoundEnd {
+        timer: Timer::from_seconds(super::ROUND_END_TIME, TimerMode::Once),
+    });
+}
+
+pub fn start_next_round(time: Res<Time>, mut round_end: ResMut<RoundEnd>, mut commands: Commands) {
+    round_end.timer.tick(time.delta());
+    if round_end.timer.finished() {
+        commands.remove_resource::<RoundEnd>();
+        arcade_game::restart_scene(&mut commands);
+    }
+}
+
+pub fn toggle_camera_layout(kb_input: Res<Input<KeyCode>>, mut layout: ResMut<CameraLayout>) {
+    if kb_input.just_pressed(super::LAYOUT_KEY) {
+        *layout = match *layout {
+            CameraLayout::Shared => CameraLayout::Split,
+            CameraLayout::Split => CameraLayout::Shared,
+        };
+    }
+}
+
+pub fn frame_players(
+    layout: Res<CameraLayout>,
+    camera_scale: Res<CameraScale>,
+    players: Query<(&PlayerId, &GlobalTransform), With<Player>>,
+    mut cameras: Query<
+        (
+            &mut Camera,
+            &mut Transform,
+            &mut OrthographicProjection,
+            Option<&PlayerCamera>,
+        ),
+        Or<(With<MainCamera>, With<PlayerCamera>)>,
+    >,
+) {
+    let positions: Vec<(PlayerId, Vec2)> = players
+        .iter()
+        .map(|(player_id, transform)| (*player_id, transform.translation().truncate()))
+        .collect();
+    if positions.is_empty() {
+        return;
+    }
+    for (mut camera, mut transform, mut projection, player_camera) in &mut cameras {
+        let (logical, physical) =
+            match (camera.logical_target_size(), camera.physical_target_size()) {
+                (Some(logical), Some(physical)) => (logical, physical),
+                _ => continue,
+            };
+        let player_id = player_camera
+            .map(|player_camera| player_camera.0)
+            .unwrap_or(PlayerId::ONE);
+        let focus = match *layout {
+            CameraLayout::Shared => {
+                if player_camera.is_some() {
+                    camera.is_active = false;
+                    continue;
+                }
+                camera.viewport = None;
+                let (min, max) = positions.iter().fold(
+                    (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+                    |(min, max), (_, position)| (min.min(*position), max.max(*position)),
+                );
+                let extent = max - min + 2.0 * super::FRAME_MARGIN * Vec2::ONE;
+                projection.scale = camera_scale
+                    .0
+                    .max(extent.x / logical.x)
+                    .max(extent.y / logical.y);
+                (min + max) / 2.0
+            }
+            CameraLayout::Split => {
+                let half_width = physical.x / 2;
+                camera.is_active = true;
+                camera.viewport = Some(Viewport {
+                    physical_position: UVec2::new(player_id.0 as u32 * half_width, 0),
+                    physical_size: UVec2::new(half_width, physical.y),
+                    ..default()
+                });
+                projection.scale = camera_scale.0;
+                match positions.iter().find(|(id, _)| *id == player_id) {
+                    Some((_, position)) => *position,
+                    None => continue,
+                }
+            }
+        };
+        transform.translation = focus.extend(transform.translation.z);
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy::window::WindowId;
+use bevy::winit::WinitWindows;
+use winit::window::Icon;
+
+const ICON_PATH: &str = "icon.png";
+
+/// Shows the pig as the window icon. Bevy has no setting for it, so it is set on the
+/// winit window once the image has loaded.
+pub struct WindowIconPlugin;
+
+impl Plugin for WindowIconPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(load_icon).add_system(set_icon);
+    }
+}
+
+#[derive(Resource)]
+struct WindowIcon {
+    image: Handle<Image>,
+    applied: bool,
+}
+
+fn load_icon(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(WindowIcon {
+        image: asset_server.load(ICON_PATH),
+        applied: false,
+    });
+}
+
+fn set_icon(
+    mut window_icon: ResMut<WindowIcon>,
+    images: Res<Assets<Image>>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    if window_icon.applied {
+        return;
+    }
+    let (image, window) = match (
+        images.get(&window_icon.image),
+        winit_windows.get_window(WindowId::primary()),
+    ) {
+        (Some(image), Some(window)) => (image, window),
+        _ => return,
+    };
+    // the png loader hands over RGBA pixels, which is what winit wants
+    let size = image.size();
+    match Icon::from_rgba(image.data.clone(), size.x as u32, size.y as u32) {
+        Ok(icon) => window.set_window_icon(Some(icon)),
+        Err(err) => error!("could not use {} as the window icon: {}", ICON_PATH, err),
+    }
+    window_icon.applied = true;
+}
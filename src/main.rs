@@ -1,33 +1,148 @@
-use arcade_game::ArcadeGame;
+#[cfg(feature = "dev")]
+use arcade_game::dev::DevLogPlugin;
+use arcade_game::{settings::Settings, ArcadeGame};
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
-use bevy::window::PresentMode;
+use bevy_ecs_ldtk::prelude::LevelSelection;
+use std::path::PathBuf;
+use tracing_subscriber::EnvFilter;
 
 mod arcade_game;
 
-const W_WIDTH: f32 = 1280.;
-const W_HEIGHT: f32 = 720.;
+const TITLE: &str = "The Adventures of Gunther the Pig";
+#[cfg(feature = "dev")]
+const LOG_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=error,gunther_adv=debug";
+#[cfg(not(feature = "dev"))]
+const LOG_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=error";
+
+const USAGE: &str = "\
+usage: gunther_adv [options]
+
+  --level <identifier|index>  start on this LDtk level
+  --map <path>                LDtk project to load, relative to assets/
+  --seed <number>             gameplay random seed
+  --skip-menu                 start playing right away
+  --replay <path>             play a recorded replay back
+  --log <filter>              log filter, e.g. info,gunther_adv=debug
+  --width <px>, --height <px> window size
+  --windowed, --borderless, --fullscreen
+  --vsync, --no-vsync
+  --integer-scaling           scale the picture in whole steps only
+  --no-pixel-perfect          draw the world at the window resolution";
+
+/// Launch options; the window flags are read by `WindowSettings::apply_args`.
+#[derive(Default)]
+struct Args {
+    level: Option<LevelSelection>,
+    map: Option<String>,
+    seed: Option<u64>,
+    skip_menu: bool,
+    replay: Option<PathBuf>,
+    log: Option<String>,
+}
+
+// runs before logging is set up, so problems go straight to stderr
+fn value<'a>(flag: &str, value: Option<&'a String>) -> Option<&'a String> {
+    if value.is_none() {
+        eprintln!("{} expects a value, ignoring it", flag);
+    }
+    value
+}
+
+fn parse_args(args: &[String]) -> Args {
+    let mut parsed = Args::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--level" => {
+                // a number picks the level by index, anything else by identifier
+                parsed.level = value(arg, args.next()).map(|level| match level.parse() {
+                    Ok(index) => LevelSelection::Index(index),
+                    Err(_) => LevelSelection::Identifier(level.clone()),
+                })
+            }
+            "--map" => parsed.map = value(arg, args.next()).cloned(),
+            "--seed" => match value(arg, args.next()).map(|seed| seed.parse()) {
+                Some(Ok(seed)) => parsed.seed = Some(seed),
+                Some(Err(_)) => eprintln!("--seed expects a number, ignoring it"),
+                None => {}
+            },
+            "--skip-menu" => parsed.skip_menu = true,
+            "--replay" => parsed.replay = value(arg, args.next()).map(PathBuf::from),
+            "--log" => parsed.log = value(arg, args.next()).cloned(),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            // window flags, read by `WindowSettings::apply_args`
+            "--width" | "--height" => {
+                args.next();
+            }
+            "--windowed" | "--fullscreen" | "--borderless" | "--vsync" | "--no-vsync"
+            | "--integer-scaling" | "--no-pixel-perfect" => {}
+            _ => eprintln!("unknown option {}, ignoring it\n{}", arg, USAGE),
+        }
+    }
+    parsed
+}
+
+/// The `--log` filter if it is valid, otherwise the default. Bevy panics on a bad filter.
+fn log_filter(log: Option<String>) -> String {
+    match log {
+        Some(filter) => match EnvFilter::try_new(&filter) {
+            Ok(_) => filter,
+            Err(err) => {
+                eprintln!(
+                    "invalid --log filter {}: {}, using the default",
+                    filter, err
+                );
+                LOG_FILTER.to_string()
+            }
+        },
+        None => LOG_FILTER.to_string(),
+    }
+}
 
 fn main() {
+    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+    let args = parse_args(&raw_args);
+    let mut settings = Settings::load();
+    settings.window.apply_args(&raw_args);
+    let log_filter = log_filter(args.log);
+    let plugins = DefaultPlugins
+        .set(WindowPlugin {
+            window: settings.window.descriptor(TITLE),
+            ..default()
+        })
+        .set(AssetPlugin {
+            // hot reload the map and gameplay tuning while iterating on them
+            watch_for_changes: cfg!(feature = "dev"),
+            ..default()
+        })
+        .set(ImagePlugin::default_nearest());
+    #[cfg(not(feature = "dev"))]
+    let plugins = plugins.set(LogPlugin {
+        filter: log_filter,
+        level: bevy::log::Level::DEBUG,
+    });
+    // logs through a subscriber that also times every system
+    #[cfg(feature = "dev")]
+    let plugins = plugins
+        .disable::<LogPlugin>()
+        .add_before::<LogPlugin, _>(DevLogPlugin {
+            filter: log_filter,
+            level: bevy::log::Level::DEBUG,
+        });
     App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(WindowPlugin {
-                    window: WindowDescriptor {
-                        width: W_WIDTH,
-                        height: W_HEIGHT,
-                        title: "Hotel California".to_string(),
-                        present_mode: PresentMode::AutoVsync,
-                        ..default()
-                    },
-                    ..default()
-                })
-                .set(ImagePlugin::default_nearest())
-                .set(LogPlugin {
-                    filter: "info,wgpu_core=warn,wgpu_hal=error,gunther_adv=debug".into(),
-                    level: bevy::log::Level::DEBUG,
-                }),
-        )
-        .add_plugin(ArcadeGame)
+        .add_plugins(plugins)
+        .add_plugin(ArcadeGame {
+            replay: args.replay,
+            settings,
+            level: args.level,
+            map: args.map,
+            seed: args.seed,
+            skip_menu: args.skip_menu,
+            ..default()
+        })
         .run();
 }